@@ -0,0 +1,102 @@
+//! Conversions for `ArrayVec`, a stack allocated alternative to `Vec` with a fixed capacity.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use ::arrayvec::ArrayVec;
+
+impl<T, const N: usize> IntoValue for ArrayVec<T, N>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let l = i32::try_from(self.len()).unwrap_or(i32::MAX);
+        let array = v8::Array::new(scope, l);
+
+        for (i, value) in self.into_iter().enumerate() {
+            let el = value.into_value(scope);
+            let idx = u32::try_from(i).unwrap_or(u32::MAX);
+            array.set_index(scope, idx, el);
+        }
+
+        array.into()
+    }
+}
+
+/// A JS array with more than `N` elements is rejected with `Error::WrongArrayLength`, before any element is
+/// converted; a shorter one is fine.
+impl<T, const N: usize> TryFromValue for ArrayVec<T, N>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if !input.is_array() {
+            return Err(Error::ExpectedArray);
+        }
+
+        let array: v8::Local<v8::Array> = input.try_cast()?;
+        let length = array.length();
+        if length as usize > N {
+            return Err(Error::WrongArrayLength {
+                expected: N,
+                actual: length as usize,
+            });
+        }
+
+        let mut result = ArrayVec::new();
+        for i in 0..length {
+            // fails when reading the element throws, e.g. in a getter
+            let element = array
+                .get_index(scope, i)
+                .ok_or(Error::MissingArrayElement { index: i })?;
+            result.push(T::try_from_value(&element, scope)?);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::arrayvec::ArrayVec;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_an_array_vec() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let full = ArrayVec::from([1, 2, 3]);
+        let value = full.clone().into_value(scope);
+        assert!(value.is_array());
+        assert_eq!(ArrayVec::<i32, 3>::try_from_value(&value, scope).unwrap(), full);
+
+        let value = setup::eval(scope, "['a']");
+        let result = ArrayVec::<String, 3>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result.as_slice(), ["a".to_string()]);
+    }
+
+    #[test]
+    fn should_reject_an_array_over_capacity() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "[1, 2, 3, 4]");
+        let err = ArrayVec::<i32, 3>::try_from_value(&value, scope).expect_err("expected too many elements");
+        assert!(matches!(err, Error::WrongArrayLength { expected: 3, actual: 4 }));
+
+        let value = setup::eval(scope, "({ length: 1 })");
+        let err = ArrayVec::<i32, 3>::try_from_value(&value, scope).expect_err("expected an array");
+        assert!(matches!(err, Error::ExpectedArray));
+    }
+}
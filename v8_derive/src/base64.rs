@@ -0,0 +1,79 @@
+//! Conversions between bytes and a base64 encoded JS string, for channels that only carry text.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use ::base64::{engine::general_purpose::STANDARD, Engine};
+
+/// A `Vec<u8>` that is converted to and from a base64 encoded JS string, with the standard alphabet and padding,
+/// instead of an array of numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Base64(pub Vec<u8>);
+
+impl From<Vec<u8>> for Base64 {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl IntoValue for Base64 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        STANDARD.encode(self.0).into_value(scope)
+    }
+}
+
+impl TryFromValue for Base64 {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if !input.is_string() {
+            return Err(Error::ExpectedString);
+        }
+
+        let encoded = input.to_rust_string_lossy(scope);
+        STANDARD.decode(encoded).map(Self).map_err(|_| Error::InvalidBase64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Base64;
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_bytes_as_base64() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let bytes = Base64(vec![0, 1, 2, 254, 255]);
+        let value = bytes.clone().into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(value.to_rust_string_lossy(scope), "AAEC/v8=");
+        assert_eq!(Base64::try_from_value(&value, scope).unwrap(), bytes);
+
+        let value = setup::eval(scope, "'aGVsbG8='");
+        assert_eq!(Base64::try_from_value(&value, scope).unwrap().0, b"hello");
+    }
+
+    #[test]
+    fn rejects_malformed_base64() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'not base64!'");
+        let err = Base64::try_from_value(&value, scope).expect_err("expected invalid base64");
+        assert!(matches!(err, Error::InvalidBase64));
+
+        let value = setup::eval(scope, "[1, 2, 3]");
+        let err = Base64::try_from_value(&value, scope).expect_err("expected a string");
+        assert!(matches!(err, Error::ExpectedString));
+    }
+}
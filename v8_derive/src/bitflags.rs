@@ -0,0 +1,80 @@
+//! Conversions for `bitflags` types, represented in JS by the number of their bits.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use ::bitflags::Flags;
+
+/// A `bitflags` type that is converted to and from its `bits()`, e.g. a `u8` or `u32` JS number.
+///
+/// Converting from JS fails with `Error::InvalidFlags` when a bit is set that is not a known flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsFlags<T>(pub T);
+
+impl<T> From<T> for JsFlags<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> IntoValue for JsFlags<T>
+where
+    T: Flags,
+    T::Bits: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.0.bits().into_value(scope)
+    }
+}
+
+impl<T> TryFromValue for JsFlags<T>
+where
+    T: Flags,
+    T::Bits: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let bits = T::Bits::try_from_value(input, scope)?;
+        T::from_bits(bits).map(Self).ok_or(Error::InvalidFlags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, JsFlags, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    ::bitflags::bitflags! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        struct Permissions: u8 {
+            const READ = 0b001;
+            const WRITE = 0b010;
+            const EXECUTE = 0b100;
+        }
+    }
+
+    #[test]
+    fn can_convert_flags() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = JsFlags(Permissions::READ | Permissions::EXECUTE).into_value(scope);
+        assert_eq!(value.int32_value(scope), Some(0b101));
+
+        let result = JsFlags::<Permissions>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result.0, Permissions::READ | Permissions::EXECUTE);
+
+        let value = setup::eval(scope, "0b1000");
+        let err = JsFlags::<Permissions>::try_from_value(&value, scope).expect_err("expected unknown bits");
+        assert!(matches!(err, Error::InvalidFlags));
+
+        // does not fit in the bits type
+        let value = setup::eval(scope, "256");
+        let err = JsFlags::<Permissions>::try_from_value(&value, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+    }
+}
@@ -0,0 +1,59 @@
+//! Conversions for `BString` and `BStr`, byte strings that are conventionally, but not necessarily, UTF-8.
+
+use crate::{errors::Result, helpers::try_as_string, IntoValue, TryFromValue};
+use ::bstr::{BStr, BString, ByteSlice};
+
+/// The bytes are decoded lossily: each invalid UTF-8 sequence becomes U+FFFD, so such a byte string does not
+/// survive the round trip.
+impl IntoValue for BString {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.as_bstr().into_value(scope)
+    }
+}
+
+/// Decoded lossily, like a `BString`.
+impl IntoValue for &BStr {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let string = self.to_str_lossy();
+        v8::String::new(scope, &string)
+            .unwrap_or(v8::String::empty(scope))
+            .into()
+    }
+}
+
+/// Like a `String`, any JS value is converted using its string representation, encoded as UTF-8.
+impl TryFromValue for BString {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        try_as_string(input, scope).map(BString::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{setup, IntoValue, TryFromValue};
+    use ::bstr::{BStr, BString};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_byte_string() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let text = BString::from("bytes ✓");
+        let value = text.clone().into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "bytes ✓");
+        assert_eq!(BString::try_from_value(&value, scope).unwrap(), text);
+
+        let value = BStr::new("borrowed").into_value(scope);
+        assert_eq!(BString::try_from_value(&value, scope).unwrap(), "borrowed");
+
+        // the invalid UTF-8 byte is replaced
+        let value = BString::from(&b"caf\xFF"[..]).into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "caf\u{FFFD}");
+        assert_eq!(BString::try_from_value(&value, scope).unwrap(), "caf\u{FFFD}");
+    }
+}
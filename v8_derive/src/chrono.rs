@@ -0,0 +1,248 @@
+//! Conversions for the `chrono` date types.
+//!
+//! A `DateTime<Utc>` is an instant, so it is converted as a JS `Date` object. `NaiveDate` and `NaiveDateTime`
+//! have no timezone, and so no instant that a `Date` could hold; they are converted as ISO 8601 strings instead,
+//! e.g. `"2024-03-01"` and `"2024-03-01T12:30:00.250"`. A `TimeDelta` is a number of milliseconds, like a
+//! `std::time::Duration`.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::{try_as_f64, try_as_string, MAX_SAFE_INTEGER_U64},
+    IntoValue, TryFromValue,
+};
+use ::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+
+/// The ISO 8601 form of a `NaiveDateTime`; the fraction is only written when it is not zero
+const NAIVE_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// JS dates have millisecond precision, so the nanoseconds below a millisecond are rounded down, e.g.
+/// `-1.9ms` becomes `-2ms`.
+impl IntoValue for DateTime<Utc> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        #[allow(clippy::cast_precision_loss)]
+        let millis = self.timestamp_millis() as f64;
+        match v8::Date::new(scope, millis) {
+            Some(date) => date.into(),
+            None => v8::undefined(scope).into(),
+        }
+    }
+}
+
+impl TryFromValue for DateTime<Utc> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, _scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if !input.is_date() {
+            return Err(Error::ExpectedDate);
+        }
+
+        let date: v8::Local<v8::Date> = input.try_cast()?;
+        let millis = date.value_of();
+
+        // an invalid date has a NaN time value
+        if !millis.is_finite() {
+            return Err(Error::OutOfRange);
+        }
+
+        // the time value of a JS date is always integral, and within ±8.64e15 ms
+        #[allow(clippy::cast_possible_truncation)]
+        DateTime::from_timestamp_millis(millis as i64).ok_or(Error::OutOfRange)
+    }
+}
+
+impl IntoValue for NaiveDate {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.to_string().into_value(scope)
+    }
+}
+
+impl TryFromValue for NaiveDate {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        value.parse().map_err(|_| Error::InvalidDate(value))
+    }
+}
+
+impl IntoValue for NaiveDateTime {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.format(NAIVE_DATETIME_FORMAT).to_string().into_value(scope)
+    }
+}
+
+impl TryFromValue for NaiveDateTime {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        value.parse().map_err(|_| Error::InvalidDate(value))
+    }
+}
+
+/// A `TimeDelta` within `±Number.MAX_SAFE_INTEGER` milliseconds is a JS number, including the sub-millisecond
+/// fraction; a longer one is a `BigInt` of whole milliseconds, since a number would not hold it exactly.
+impl IntoValue for TimeDelta {
+    #[allow(clippy::cast_precision_loss)]
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let millis = self.num_milliseconds();
+        if millis.unsigned_abs() > MAX_SAFE_INTEGER_U64 {
+            return v8::BigInt::new_from_i64(scope, millis).into();
+        }
+
+        let millis = self.num_seconds() as f64 * 1000.0 + f64::from(self.subsec_nanos()) / 1_000_000.0;
+        millis.into_value(scope)
+    }
+}
+
+/// Reads a number of milliseconds, or a `BigInt` of them; a value beyond the range of a `TimeDelta` is rejected
+/// with `Error::OutOfRange`.
+impl TryFromValue for TimeDelta {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if input.is_big_int() {
+            let big_int: v8::Local<v8::BigInt> = input.try_cast()?;
+            let (millis, true) = big_int.i64_value() else {
+                return Err(Error::OutOfRange);
+            };
+            return TimeDelta::try_milliseconds(millis).ok_or(Error::OutOfRange);
+        }
+
+        let millis = try_as_f64(input, scope)?;
+        if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+            return Err(Error::OutOfRange);
+        }
+
+        let whole_millis = millis.trunc();
+        let nanos = ((millis - whole_millis) * 1_000_000.0).round();
+        TimeDelta::try_milliseconds(whole_millis as i64)
+            .and_then(|delta| delta.checked_add(&TimeDelta::nanoseconds(nanos as i64)))
+            .ok_or(Error::OutOfRange)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Utc};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn can_convert_a_utc_datetime() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // 2024-03-01T10:30:00.250Z
+        let datetime = DateTime::from_timestamp_millis(1_709_289_000_250).unwrap();
+        let js_date = datetime.into_value(scope);
+        assert!(js_date.is_date());
+
+        let js_date: v8::Local<v8::Date> = js_date.try_into().unwrap();
+        assert_eq!(js_date.value_of(), 1_709_289_000_250.0);
+
+        let result = DateTime::<Utc>::try_from_value(&js_date.into(), scope).unwrap();
+        assert_eq!(result, datetime);
+    }
+
+    #[test]
+    fn can_convert_a_naive_date() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let value = date.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "2024-03-01");
+
+        let result = NaiveDate::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, date);
+    }
+
+    #[test]
+    fn can_convert_a_naive_datetime() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let datetime = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_milli_opt(12, 30, 0, 250)
+            .unwrap();
+        let value = datetime.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "2024-03-01T12:30:00.250");
+
+        let result = NaiveDateTime::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, datetime);
+
+        let value = setup::eval(scope, "'1999-12-31T23:59:00'");
+        let result = NaiveDateTime::try_from_value(&value, scope).unwrap();
+        assert_eq!(
+            result,
+            NaiveDate::from_ymd_opt(1999, 12, 31)
+                .unwrap()
+                .and_hms_opt(23, 59, 0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_dates() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'2024-02-30'");
+        let err = NaiveDate::try_from_value(&value, scope).expect_err("expected an invalid date");
+        assert!(matches!(err, Error::InvalidDate(date) if date == "2024-02-30"));
+
+        let value = setup::eval(scope, "'2024-03-01 noon'");
+        let err = NaiveDateTime::try_from_value(&value, scope).expect_err("expected an invalid datetime");
+        assert!(matches!(err, Error::InvalidDate(_)));
+
+        let js_date = setup::eval(scope, "new Date('not a date')");
+        let err = DateTime::<Utc>::try_from_value(&js_date, scope).expect_err("expected an invalid date");
+        assert!(matches!(err, Error::OutOfRange));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn can_convert_a_time_delta() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // 3 days, 4 hours, 5 minutes and 6.789 seconds
+        let delta = TimeDelta::days(3) + TimeDelta::hours(4) + TimeDelta::minutes(5) + TimeDelta::milliseconds(6_789);
+        let value = delta.into_value(scope);
+        assert!(value.is_number());
+        assert_eq!(value.number_value(scope), Some(274_506_789.0));
+        assert_eq!(TimeDelta::try_from_value(&value, scope).unwrap(), delta);
+
+        let delta = -TimeDelta::days(2) - TimeDelta::microseconds(1_500);
+        let value = delta.into_value(scope);
+        assert_eq!(value.number_value(scope), Some(-172_800_001.5));
+        assert_eq!(TimeDelta::try_from_value(&value, scope).unwrap(), delta);
+
+        // beyond Number.MAX_SAFE_INTEGER milliseconds
+        let delta = TimeDelta::try_days(200_000_000).unwrap();
+        let value = delta.into_value(scope);
+        assert!(value.is_big_int());
+        assert_eq!(value.to_rust_string_lossy(scope), "17280000000000000");
+        assert_eq!(TimeDelta::try_from_value(&value, scope).unwrap(), delta);
+
+        let value = setup::eval(scope, "2n ** 64n");
+        let err = TimeDelta::try_from_value(&value, scope).expect_err("expected an out of range error");
+        assert!(matches!(err, Error::OutOfRange));
+    }
+}
@@ -0,0 +1,117 @@
+//! Registering JS classes, so derived structs can be converted to and from instances of them.
+
+use crate::errors::{Error, Result};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The constructors registered in a context, by the name used in `#[v8(class = "...")]`
+#[derive(Default)]
+struct ClassRegistry(RefCell<HashMap<String, v8::Global<v8::Function>>>);
+
+/// Registers the constructor of a JS class in the current context, under the name that a struct refers to
+/// with `#[v8(class = "Foo")]`. Registering the same name again replaces the constructor.
+pub fn register_class(scope: &mut v8::PinScope<'_, '_>, name: &str, constructor: v8::Local<'_, v8::Function>) {
+    let context = scope.get_current_context();
+    let registry = context.get_slot::<ClassRegistry>().unwrap_or_else(|| {
+        let registry = Rc::new(ClassRegistry::default());
+        context.set_slot(registry.clone());
+        registry
+    });
+
+    let constructor = v8::Global::new(scope, constructor);
+    registry.0.borrow_mut().insert(name.to_string(), constructor);
+}
+
+fn constructor<'s>(name: &str, scope: &mut v8::PinScope<'s, '_>) -> Option<v8::Local<'s, v8::Function>> {
+    let registry = scope.get_current_context().get_slot::<ClassRegistry>()?;
+    let constructors = registry.0.borrow();
+    constructors
+        .get(name)
+        .map(|constructor| v8::Local::new(scope, constructor))
+}
+
+/// Constructs an instance of the registered class, calling its constructor without arguments.
+///
+/// Returns `None` when no class is registered under the name, or the constructor throws.
+pub fn new_instance<'s>(name: &str, scope: &mut v8::PinScope<'s, '_>) -> Option<v8::Local<'s, v8::Object>> {
+    constructor(name, scope)?.new_instance(scope, &[])
+}
+
+/// Checks that the JS value is an instance of the registered class, or of a subclass of it.
+///
+/// # Errors
+/// `Error::UnregisteredClass` when no class is registered under the name, and `Error::ExpectedInstance`
+/// when the value is not an instance
+pub fn expect_instance(name: &str, input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<()> {
+    let Some(constructor) = constructor(name, scope) else {
+        return Err(Error::UnregisteredClass(name.to_string()));
+    };
+
+    if input.instance_of(scope, constructor.into()) == Some(true) {
+        Ok(())
+    } else {
+        Err(Error::ExpectedInstance(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as v8_derive, errors::Error, register_class, setup, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(class = "Greeter")]
+    struct Greeter {
+        name: String,
+    }
+
+    #[test]
+    fn can_convert_a_class_instance() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let constructor = setup::eval(
+            scope,
+            "class Greeter { greet() { return `Hello ${this.name}`; } }; Greeter",
+        );
+        let constructor = v8::Local::<v8::Function>::try_from(constructor).unwrap();
+        register_class(scope, "Greeter", constructor);
+
+        let greeter = Greeter {
+            name: "world".to_string(),
+        };
+        let value = greeter.into_value(scope);
+        assert_eq!(value.instance_of(scope, constructor.into()), Some(true));
+
+        let js_key = v8::String::new(scope, "greeter").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let greeting = setup::eval(scope, "greeter.greet()");
+        assert_eq!(greeting.to_rust_string_lossy(scope), "Hello world");
+
+        let value = setup::eval(scope, "Object.assign(new Greeter(), { name: 'JS' })");
+        let greeter = Greeter::try_from_value(&value, scope).unwrap();
+        assert_eq!(greeter.name, "JS");
+
+        let value = setup::eval(scope, "({ name: 'plain' })");
+        let err = Greeter::try_from_value(&value, scope).expect_err("expected an instance");
+        assert!(matches!(err, Error::ExpectedInstance(name) if name == "Greeter"));
+    }
+
+    #[test]
+    fn requires_a_registered_class() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({ name: 'plain' })");
+        let err = Greeter::try_from_value(&value, scope).expect_err("expected an unregistered class");
+        assert!(matches!(err, Error::UnregisteredClass(name) if name == "Greeter"));
+    }
+}
@@ -0,0 +1,59 @@
+//! Conversions for `CompactString`, which keeps short strings inline instead of on the heap.
+
+use crate::{errors::Result, IntoValue, TryFromValue};
+use ::compact_str::CompactString;
+use std::mem::MaybeUninit;
+
+/// Strings up to this many UTF-8 bytes are stored inline by `CompactString`
+const INLINE_CAPACITY: usize = size_of::<CompactString>();
+
+impl IntoValue for CompactString {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        v8::String::new(scope, &self).unwrap_or(v8::String::empty(scope)).into()
+    }
+}
+
+/// Like a `String`, any JS value is converted using its string representation. A short string is decoded
+/// into a stack buffer, so it never touches the heap.
+impl TryFromValue for CompactString {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let Some(string) = input.to_string(scope) else {
+            return Ok(CompactString::default());
+        };
+
+        let mut buffer = [MaybeUninit::uninit(); INLINE_CAPACITY];
+        Ok(CompactString::from(string.to_rust_cow_lossy(scope, &mut buffer)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{setup, IntoValue, TryFromValue};
+    use ::compact_str::CompactString;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_compact_string() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let short = CompactString::from("short");
+        let value = short.clone().into_value(scope);
+        let result = CompactString::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, short);
+        assert!(!result.is_heap_allocated());
+
+        let long = CompactString::from("a string that is too long to be stored inline ✓");
+        let value = long.clone().into_value(scope);
+        let result = CompactString::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, long);
+        assert!(result.is_heap_allocated());
+
+        let value = 42.into_value(scope);
+        assert_eq!(CompactString::try_from_value(&value, scope).unwrap(), "42");
+    }
+}
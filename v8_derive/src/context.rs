@@ -0,0 +1,275 @@
+//! Conversions that thread a user provided context through, e.g. an interning table or a symbol registry.
+//!
+//! The derive macros implement these traits for a struct with the `#[v8(ctx = "MyCtx")]` attribute. Every field
+//! is still converted with `TryFromValue` and `IntoValue`, except the ones marked `#[v8(with_ctx)]`, which are
+//! converted with these traits and so receive the context as well.
+
+use crate::errors::{Error, Result};
+
+/// The hooks that a context receives during a derived conversion.
+pub trait ConversionContext {
+    /// Called with the JS property name of each field, after the field was converted in either direction.
+    fn field_converted(&mut self, name: &str) {
+        let _ = name;
+    }
+}
+
+/// Like `TryFromValue`, with a context to read from and write to.
+pub trait TryFromValueWithCtx<Ctx: ?Sized>: Sized {
+    /// Converts a `v8::Value` into a Rust type, with the context.
+    ///
+    /// # Errors
+    /// In case of conversion errors, or if the value is not supported, an error is returned.
+    fn try_from_value_with_ctx(
+        input: &v8::Local<'_, v8::Value>,
+        scope: &mut v8::PinScope<'_, '_>,
+        ctx: &mut Ctx,
+    ) -> Result<Self>;
+}
+
+/// Like `IntoValue`, with a context to read from and write to.
+pub trait IntoValueWithCtx<Ctx: ?Sized> {
+    fn into_value_with_ctx<'s>(self, scope: &mut v8::PinScope<'s, '_>, ctx: &mut Ctx) -> v8::Local<'s, v8::Value>;
+}
+
+/// `null` and `undefined` are converted into `None`, like `TryFromValue` does.
+impl<T, Ctx> TryFromValueWithCtx<Ctx> for Option<T>
+where
+    T: TryFromValueWithCtx<Ctx>,
+    Ctx: ?Sized,
+{
+    fn try_from_value_with_ctx(
+        input: &v8::Local<'_, v8::Value>,
+        scope: &mut v8::PinScope<'_, '_>,
+        ctx: &mut Ctx,
+    ) -> Result<Self> {
+        if input.is_null_or_undefined() {
+            return Ok(None);
+        }
+
+        T::try_from_value_with_ctx(input, scope, ctx).map(Some)
+    }
+}
+
+impl<T, Ctx> TryFromValueWithCtx<Ctx> for Vec<T>
+where
+    T: TryFromValueWithCtx<Ctx>,
+    Ctx: ?Sized,
+{
+    fn try_from_value_with_ctx(
+        input: &v8::Local<'_, v8::Value>,
+        scope: &mut v8::PinScope<'_, '_>,
+        ctx: &mut Ctx,
+    ) -> Result<Self> {
+        if !input.is_array() {
+            return Err(Error::ExpectedArray);
+        }
+
+        let array: v8::Local<v8::Array> = input.try_cast()?;
+        let length = array.length();
+
+        let mut result = Vec::with_capacity(length as usize);
+
+        for i in 0..length {
+            let element = array
+                .get_index(scope, i)
+                .ok_or(Error::MissingArrayElement { index: i })?;
+
+            result.push(T::try_from_value_with_ctx(&element, scope, ctx)?);
+        }
+
+        Ok(result)
+    }
+}
+
+impl<T, Ctx> IntoValueWithCtx<Ctx> for Option<T>
+where
+    T: IntoValueWithCtx<Ctx>,
+    Ctx: ?Sized,
+{
+    fn into_value_with_ctx<'s>(self, scope: &mut v8::PinScope<'s, '_>, ctx: &mut Ctx) -> v8::Local<'s, v8::Value> {
+        match self {
+            Some(value) => value.into_value_with_ctx(scope, ctx),
+            None => v8::null(scope).into(),
+        }
+    }
+}
+
+impl<T, Ctx> IntoValueWithCtx<Ctx> for Vec<T>
+where
+    T: IntoValueWithCtx<Ctx>,
+    Ctx: ?Sized,
+{
+    fn into_value_with_ctx<'s>(self, scope: &mut v8::PinScope<'s, '_>, ctx: &mut Ctx) -> v8::Local<'s, v8::Value> {
+        let l = i32::try_from(self.len()).unwrap_or(i32::MAX);
+        let array = v8::Array::new(scope, l);
+
+        for (i, value) in self.into_iter().enumerate() {
+            let el = value.into_value_with_ctx(scope, ctx);
+            let idx = u32::try_from(i).unwrap_or(u32::MAX);
+            array.set_index(scope, idx, el);
+        }
+
+        array.into()
+    }
+}
+
+/// Reads the property `field_name` of the object, and converts it with the context; used by the derive macros
+/// for the fields marked `#[v8(with_ctx)]`.
+///
+/// # Errors
+/// `Error::ExpectedObject` when the value is not an object, or the conversion error of the property
+pub fn get_field_with_ctx<T, Ctx>(
+    field_name: &str,
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    ctx: &mut Ctx,
+) -> Result<T>
+where
+    T: TryFromValueWithCtx<Ctx>,
+    Ctx: ?Sized,
+{
+    if !input.is_object() {
+        return Err(Error::ExpectedObject);
+    }
+
+    let js_object: v8::Local<v8::Object> = input.try_cast()?;
+    let js_key = v8::String::new(scope, field_name)
+        .map(Into::into)
+        .ok_or(Error::InvalidField(field_name.to_string()))?;
+    let js_value = js_object
+        .get(scope, js_key)
+        .ok_or(Error::FieldNotFound(field_name.to_string()))?;
+
+    T::try_from_value_with_ctx(&js_value, scope, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConversionContext, IntoValueWithCtx, TryFromValueWithCtx};
+    use crate::{self as v8_derive, errors::Result, from::TryFromValue, helpers::try_as_string, setup, IntoValue};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    /// Interns the names of the symbols, and counts the converted fields
+    #[derive(Default)]
+    struct Interner {
+        names: Vec<String>,
+        fields: Vec<String>,
+    }
+
+    impl ConversionContext for Interner {
+        fn field_converted(&mut self, name: &str) {
+            self.fields.push(name.to_string());
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Symbol(usize);
+
+    impl TryFromValueWithCtx<Interner> for Symbol {
+        fn try_from_value_with_ctx(
+            input: &v8::Local<'_, v8::Value>,
+            scope: &mut v8::PinScope<'_, '_>,
+            ctx: &mut Interner,
+        ) -> Result<Self> {
+            let name = try_as_string(input, scope)?;
+            let index = ctx.names.iter().position(|known| *known == name).unwrap_or_else(|| {
+                ctx.names.push(name);
+                ctx.names.len() - 1
+            });
+            Ok(Symbol(index))
+        }
+    }
+
+    impl IntoValueWithCtx<Interner> for Symbol {
+        fn into_value_with_ctx<'s>(
+            self,
+            scope: &mut v8::PinScope<'s, '_>,
+            ctx: &mut Interner,
+        ) -> v8::Local<'s, v8::Value> {
+            ctx.names[self.0].clone().into_value(scope)
+        }
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(ctx = "Interner")]
+    struct Token {
+        #[v8(with_ctx)]
+        kind: Symbol,
+        #[v8(with_ctx)]
+        tags: Vec<Symbol>,
+        line: u32,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(ctx = "Interner", rename_all = "camelCase")]
+    struct Position {
+        line_number: u32,
+        column: u32,
+    }
+
+    #[test]
+    fn should_convert_with_the_context() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let mut interner = Interner::default();
+        let value = setup::eval(scope, "({ kind: 'ident', tags: ['keyword', 'ident'], line: 3 })");
+        let token = Token::try_from_value_with_ctx(&value, scope, &mut interner).unwrap();
+        assert_eq!(
+            token,
+            Token {
+                kind: Symbol(0),
+                tags: vec![Symbol(1), Symbol(0)],
+                line: 3,
+            }
+        );
+        assert_eq!(interner.names, vec!["ident", "keyword"]);
+        assert_eq!(interner.fields, vec!["kind", "tags", "line"]);
+
+        interner.fields.clear();
+        let value = token.into_value_with_ctx(scope, &mut interner);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(
+            json.to_rust_string_lossy(scope),
+            r#"{"kind":"ident","tags":["keyword","ident"],"line":3}"#
+        );
+        assert_eq!(interner.fields, vec!["kind", "tags", "line"]);
+    }
+
+    #[test]
+    fn should_convert_without_the_context() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // without with_ctx fields, the struct also converts like any other
+        let value = setup::eval(scope, "({ lineNumber: 7, column: 12 })");
+        let position = Position::try_from_value(&value, scope).unwrap();
+        assert_eq!(
+            position,
+            Position {
+                line_number: 7,
+                column: 12,
+            }
+        );
+
+        let mut interner = Interner::default();
+        let position = Position::try_from_value_with_ctx(&value, scope, &mut interner).unwrap();
+        assert_eq!(interner.fields, vec!["lineNumber", "column"]);
+
+        let value = position.into_value_with_ctx(scope, &mut interner);
+        assert!(value.is_object());
+        assert_eq!(interner.fields, vec!["lineNumber", "column", "lineNumber", "column"]);
+    }
+}
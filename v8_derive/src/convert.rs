@@ -0,0 +1,32 @@
+//! The conversion functions used by the derive macros, for implementing `TryFromValue` by hand.
+//!
+//! The signatures of these functions are stable. The scalar `try_as_*` functions, e.g. [`try_as_f64`] and
+//! [`try_as_string`], match [`ParseFn`], so they can also be passed to [`get_field_as`] and friends.
+//!
+//! ```
+//! use v8_derive::{convert, errors, TryFromValue};
+//!
+//! struct Temperature {
+//!     celsius: f64,
+//!     unit: String,
+//! }
+//!
+//! impl TryFromValue for Temperature {
+//!     fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+//!         let celsius = convert::get_field_as("celsius", input, scope, convert::try_as_f64)?;
+//!         let unit = convert::get_optional_field_as("unit", input, scope, convert::try_as_string)?;
+//!         Ok(Self {
+//!             celsius,
+//!             unit: unit.unwrap_or_else(|| "C".to_string()),
+//!         })
+//!     }
+//! }
+//! ```
+
+pub use crate::helpers::{
+    get_field_as, get_optional_field_as, get_optional_path_as, get_path_as, try_as_array, try_as_array_of_length,
+    try_as_bool, try_as_duration, try_as_f32, try_as_f64, try_as_hashmap, try_as_hashmap_keyed, try_as_i32,
+    try_as_i32_coerce, try_as_i64, try_as_i64_strict, try_as_i8, try_as_ip_addr, try_as_ipv4_addr, try_as_ipv6_addr,
+    try_as_numeric_string, try_as_socket_addr, try_as_string, try_as_u16, try_as_u32, try_as_u32_coerce, try_as_u64,
+    try_as_u64_strict, try_as_u8, try_as_vec, try_as_vec_skip_holes, ParseFn,
+};
@@ -0,0 +1,83 @@
+//! Converting JS values within a deadline, e.g. for untrusted input whose getters or proxy traps could run forever.
+
+use crate::{
+    errors::{Error, Result},
+    TryFromValue,
+};
+use std::{sync::mpsc, thread, time::Instant};
+
+/// Converts the JS value into `T`, terminating the JS execution of the isolate when the conversion is still running
+/// at the `deadline`, e.g. in a getter that never returns.
+///
+/// A watchdog thread waits for the deadline, and calls `terminate_execution` on the isolate if the conversion has
+/// not finished by then; the termination is cancelled again before returning, so the isolate stays usable.
+///
+/// # Errors
+/// `Error::Timeout` when the deadline passed before the conversion finished, or the conversion error
+pub fn try_from_value_with_deadline<T>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    deadline: Instant,
+) -> Result<T>
+where
+    T: TryFromValue,
+{
+    let isolate = scope.thread_safe_handle();
+    let (done, finished) = mpsc::channel::<()>();
+    let watchdog = thread::spawn(move || {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        // the sender is dropped when the conversion finished in time
+        let timed_out = matches!(finished.recv_timeout(timeout), Err(mpsc::RecvTimeoutError::Timeout));
+        if timed_out {
+            isolate.terminate_execution();
+        }
+        timed_out
+    });
+
+    let result = T::try_from_value(input, scope);
+    drop(done);
+    let timed_out = watchdog.join().unwrap_or(false);
+
+    if timed_out {
+        scope.cancel_terminate_execution();
+        // a conversion that finished just before the termination still counts
+        return result.map_err(|_| Error::Timeout);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::try_from_value_with_deadline;
+    use crate::{self as v8_derive, errors::Error, setup};
+    use std::time::{Duration, Instant};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::FromValue;
+
+    #[derive(Debug, FromValue)]
+    struct Reading {
+        value: i32,
+    }
+
+    #[test]
+    fn should_time_out_in_a_slow_getter() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({ get value() { while (true) {} } })");
+        let deadline = Instant::now() + Duration::from_millis(50);
+        let err = try_from_value_with_deadline::<Reading>(&value, scope, deadline).expect_err("expected a timeout");
+        assert!(matches!(err, Error::Timeout));
+
+        // the isolate can run JS again
+        let value = setup::eval(scope, "({ value: 7 })");
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let reading = try_from_value_with_deadline::<Reading>(&value, scope, deadline).unwrap();
+        assert_eq!(reading.value, 7);
+    }
+}
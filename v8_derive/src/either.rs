@@ -0,0 +1,107 @@
+//! Conversions for `Either`, for values that can have one of two shapes.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use ::either::Either;
+
+impl<L, R> IntoValue for Either<L, R>
+where
+    L: IntoValue,
+    R: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        match self {
+            Either::Left(value) => value.into_value(scope),
+            Either::Right(value) => value.into_value(scope),
+        }
+    }
+}
+
+/// `L` is tried first, then `R`. Keep in mind that some conversions are lenient, e.g. a `String` accepts any
+/// JS value, so the stricter type should be on the left.
+impl<L, R> TryFromValue for Either<L, R>
+where
+    L: TryFromValue,
+    R: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let left = match L::try_from_value(input, scope) {
+            Ok(value) => return Ok(Either::Left(value)),
+            Err(err) => err,
+        };
+
+        let right = match R::try_from_value(input, scope) {
+            Ok(value) => return Ok(Either::Right(value)),
+            Err(err) => err,
+        };
+
+        Err(Error::NeitherMatched {
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as v8_derive, errors::Error, setup, IntoValue, TryFromValue};
+    use ::either::Either;
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Person {
+        name: String,
+    }
+
+    #[test]
+    fn can_convert_either_side() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "[1, 2]");
+        let result = Either::<Vec<i32>, Person>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, Either::Left(vec![1, 2]));
+
+        let value = setup::eval(scope, "({ name: 'Marcel' })");
+        let result = Either::<Vec<i32>, Person>::try_from_value(&value, scope).unwrap();
+        assert_eq!(
+            result,
+            Either::Right(Person {
+                name: "Marcel".to_string()
+            })
+        );
+
+        let value = Either::<Vec<i32>, Person>::Right(Person {
+            name: "John".to_string(),
+        })
+        .into_value(scope);
+        assert!(value.is_object() && !value.is_array());
+        let value = Either::<Vec<i32>, Person>::Left(vec![3]).into_value(scope);
+        assert!(value.is_array());
+    }
+
+    #[test]
+    fn should_fail_when_neither_side_matches() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'neither'");
+        let err = Either::<Vec<i32>, Person>::try_from_value(&value, scope).expect_err("expected no match");
+        let Error::NeitherMatched { left, right } = err else {
+            panic!("expected NeitherMatched, got {err:?}");
+        };
+        assert!(matches!(*left, Error::ExpectedArray));
+        assert!(matches!(*right, Error::ExpectedObject));
+    }
+}
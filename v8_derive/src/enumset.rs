@@ -0,0 +1,141 @@
+//! Conversions for `enumset` sets, represented in JS by an array of their variants, or by a bitmask number.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::{try_as_u64_strict, MAX_SAFE_INTEGER_U64},
+    try_as_vec, IntoValue, TryFromValue,
+};
+use ::enumset::{EnumSet, EnumSetType};
+
+/// Converted as an array of the variants in the set, e.g. `['Read', 'Write']` for a derived unit enum.
+impl<T> IntoValue for EnumSet<T>
+where
+    T: EnumSetType + IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.iter().collect::<Vec<T>>().into_value(scope)
+    }
+}
+
+/// Every element is converted with the conversion of the variant, so an unknown variant name is rejected with
+/// `Error::UnknownVariant` for a derived unit enum.
+impl<T> TryFromValue for EnumSet<T>
+where
+    T: EnumSetType + TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let variants: Vec<T> = try_as_vec(input, scope)?;
+        Ok(variants.into_iter().collect())
+    }
+}
+
+/// An `EnumSet` that is converted to and from a bitmask with a bit per variant, as a JS number. A mask beyond
+/// `Number.MAX_SAFE_INTEGER` is converted as a `BigInt`, and either is accepted when converting from JS.
+///
+/// Converting from JS fails with `Error::InvalidFlags` when a bit is set that is not a known variant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsEnumBits<T: EnumSetType>(pub EnumSet<T>);
+
+impl<T: EnumSetType> From<EnumSet<T>> for JsEnumBits<T> {
+    fn from(value: EnumSet<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: EnumSetType> IntoValue for JsEnumBits<T> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        match self.0.try_as_u64() {
+            // a safe integer is exactly representable as a JS number
+            #[allow(clippy::cast_precision_loss)]
+            Some(bits) if bits <= MAX_SAFE_INTEGER_U64 => v8::Number::new(scope, bits as f64).into(),
+            Some(bits) => v8::BigInt::new_from_u64(scope, bits).into(),
+            None => match v8::BigInt::new_from_words(scope, false, &self.0.to_vec()) {
+                Some(bits) => bits.into(),
+                None => v8::undefined(scope).into(),
+            },
+        }
+    }
+}
+
+impl<T: EnumSetType> TryFromValue for JsEnumBits<T> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if input.is_big_int() {
+            let bits: v8::Local<v8::BigInt> = input.try_cast()?;
+            let mut words = vec![0; bits.word_count()];
+            let (negative, words) = bits.to_words_array(&mut words);
+            if negative {
+                return Err(Error::OutOfRange);
+            }
+
+            return EnumSet::try_from_slice(words).map(Self).ok_or(Error::InvalidFlags);
+        }
+
+        let bits = try_as_u64_strict(input, scope)?;
+        EnumSet::try_from_u64(bits).map(Self).ok_or(Error::InvalidFlags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as v8_derive, errors::Error, setup, IntoValue, JsEnumBits, TryFromValue};
+    use ::enumset::{EnumSet, EnumSetType};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    #[derive(EnumSetType, Debug, FromValue, IntoValue)]
+    enum Permission {
+        Read,
+        Write,
+        Execute,
+    }
+
+    #[test]
+    fn can_convert_a_set_of_variant_names() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let permissions = Permission::Read | Permission::Execute;
+        let value = permissions.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(json.to_rust_string_lossy(scope), r#"["Read","Execute"]"#);
+
+        let result = EnumSet::<Permission>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, permissions);
+
+        let value = setup::eval(scope, "['Write', 'Delete']");
+        let err = EnumSet::<Permission>::try_from_value(&value, scope).expect_err("expected an unknown variant");
+        assert!(matches!(err.without_preview(), Error::UnknownVariant(_)));
+    }
+
+    #[test]
+    fn can_convert_a_set_as_a_bitmask() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let permissions = Permission::Read | Permission::Execute;
+        let value = JsEnumBits(permissions).into_value(scope);
+        assert!(value.is_number());
+        assert_eq!(value.int32_value(scope), Some(0b101));
+
+        let result = JsEnumBits::<Permission>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result.0, permissions);
+
+        let value = setup::eval(scope, "0b011n");
+        let result = JsEnumBits::<Permission>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result.0, Permission::Read | Permission::Write);
+
+        let value = setup::eval(scope, "0b1000");
+        let err = JsEnumBits::<Permission>::try_from_value(&value, scope).expect_err("expected unknown bits");
+        assert!(matches!(err, Error::InvalidFlags));
+    }
+}
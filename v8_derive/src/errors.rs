@@ -25,16 +25,85 @@ pub enum Error {
     ExpectedU32,
     #[error("Conversion failed; Expected BigInt")]
     ExpectedI64,
+    #[error("Conversion failed; Expected unsigned BigInt")]
+    ExpectedU64,
     #[error("Conversion failed; Expected Number")]
     ExpectedF64,
     #[error("Conversion failed; Value out of range")]
     OutOfRange,
+    #[error("Conversion failed; Expected a number other than NaN")]
+    NonFiniteNumber,
+    #[error("Conversion failed; The number is not exactly an integer")]
+    LossyConversion,
+    #[error("Conversion failed; Expected Date")]
+    ExpectedDate,
+    #[error("Conversion failed; Expected {0}")]
+    ExpectedTypedArray(&'static str),
+    #[error("Conversion failed; Unknown flag bits")]
+    InvalidFlags,
+    #[error("Conversion failed; Expected Promise")]
+    ExpectedPromise,
     #[error("Conversion failed; Expected Map")]
     ExpectedMap,
+    #[error("Conversion failed; Expected Set")]
+    ExpectedSet,
+    #[error("Conversion failed; Expected an iterable")]
+    NotIterable,
     #[error("Conversion failed; Failed to get property names")]
     FailedToGetPropertyNames,
     #[error("Conversion failed; Unsupported value type")]
     UnsupportedValueType,
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid network: {0}")]
+    InvalidNetwork(String),
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+    #[error("Conversion failed; Invalid base64")]
+    InvalidBase64,
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(String),
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
+    #[error("Invalid number: {0}")]
+    InvalidNumber(String),
+    #[error("Conversion failed; Expected an instance of {0}")]
+    ExpectedInstance(String),
+    #[error("Class not registered: {0}")]
+    UnregisteredClass(String),
+    #[error("Promise rejected: {reason}")]
+    PromiseRejected { reason: String },
+    #[error("Promise is still pending")]
+    PromisePending,
+    #[error("Unknown variant: {0}")]
+    UnknownVariant(String),
+    #[error("Conversion failed; Expected the tag {expected}, got {actual}")]
+    TagMismatch { expected: String, actual: String },
+    #[error("Validation failed: {0}")]
+    Validation(String),
+    #[error("Conversion failed; Failed to get the array element at index {index}")]
+    MissingArrayElement { index: u32 },
+    #[error("Conversion failed; Expected an array of length {expected}, got {actual}")]
+    WrongArrayLength { expected: usize, actual: usize },
+    #[error("Conversion failed; The deadline passed")]
+    Timeout,
+    #[error("JS exception: {message}")]
+    JsException { message: String, stack: Option<String> },
+    #[error("Conversion failed; Neither side matched: {left}; {right}")]
+    NeitherMatched { left: Box<Error>, right: Box<Error> },
+    #[error("{source}; got {preview}")]
+    WithPreview { preview: String, source: Box<Error> },
+}
+
+impl Error {
+    /// The error without the preview of the offending JS value, if one was attached
+    #[must_use]
+    pub fn without_preview(&self) -> &Error {
+        match self {
+            Error::WithPreview { source, .. } => source.without_preview(),
+            error => error,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,64 @@
+//! Surfacing Rust errors to JS as `Error` objects.
+
+use crate::IntoValue;
+
+/// Converts the error into a JS `Error` object, with the `Display` string of the error as its message
+impl IntoValue for &(dyn std::error::Error + '_) {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let message = v8::String::new(scope, &self.to_string()).unwrap_or(v8::String::empty(scope));
+        v8::Exception::error(scope, message)
+    }
+}
+
+/// Throws the error as a JS `Error` in the current scope, e.g. from a function callback.
+///
+/// The exception is picked up by the enclosing `TryCatch`, or propagates to the calling JS code.
+pub fn throw_error(scope: &mut v8::PinScope<'_, '_>, err: &dyn std::error::Error) {
+    let exception = err.into_value(scope);
+    scope.throw_exception(exception);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, throw_error, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_an_error_into_a_js_error() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let err = Error::FieldNotFound("name".to_string());
+        let value = (&err as &dyn std::error::Error).into_value(scope);
+        assert!(value.is_native_error());
+
+        let js_key = v8::String::new(scope, "message").unwrap().into();
+        let message = value.to_object(scope).unwrap().get(scope, js_key).unwrap();
+        assert_eq!(String::try_from_value(&message, scope).unwrap(), "Field name not found");
+    }
+
+    #[test]
+    fn can_throw_an_error() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        v8::tc_scope!(let tc, scope);
+        throw_error(tc, &Error::ExpectedObject);
+        assert!(tc.has_caught());
+
+        let exception = tc.exception().unwrap();
+        assert!(exception.is_native_error());
+        assert_eq!(
+            exception.to_rust_string_lossy(tc),
+            "Error: Conversion failed; Expected Object"
+        );
+    }
+}
@@ -1,16 +1,26 @@
 //! This module contains the `TryFromValue` trait which is used to convert a `v8::Value` into a Rust type.
 
 #[cfg(feature = "json")]
-use crate::json::v8_to_json_value;
+use crate::json::{v8_object_to_json_map, v8_to_json_value};
 use crate::{
     errors,
     helpers::{
-        try_as_bool, try_as_f32, try_as_f64, try_as_hashmap, try_as_i32, try_as_i64, try_as_i8, try_as_string,
-        try_as_u32,
+        get_element_as, get_field_as, get_optional_field_as, try_as_array_of_length, try_as_bool, try_as_duration,
+        try_as_f32, try_as_f64, try_as_hashmap_keyed, try_as_i32, try_as_i64, try_as_i8, try_as_ip_addr,
+        try_as_ipv4_addr, try_as_ipv6_addr, try_as_socket_addr, try_as_string, try_as_u16, try_as_u32, try_as_u64,
+        try_as_u8,
     },
     try_as_vec,
 };
-use std::{collections::HashMap, hash::BuildHasher};
+use std::{
+    collections::{BinaryHeap, HashMap},
+    hash::{BuildHasher, Hash},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    num::Wrapping,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::Duration,
+};
 
 /// The `TryFromValue` trait is used to convert a `v8::Value` into a Rust type.
 pub trait TryFromValue {
@@ -18,47 +28,73 @@ pub trait TryFromValue {
     ///
     /// # Errors
     /// In case of conversion errors, or if the value is not supported, an error is returned.
-    fn try_from_value(
-        input: &v8::Local<'_, v8::Value>,
-        scope: &mut v8::PinScope<'_, '_>,
-    ) -> errors::Result<Self>
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self>
     where
         Self: Sized;
 }
 
+/// Converts an owned `v8::Local` into `T`; the same as `T::try_from_value(&value, scope)`, for the call sites
+/// where the borrow is awkward, e.g. in an iterator chain.
+///
+/// ```
+/// use v8_derive::{errors, from_owned};
+///
+/// fn to_numbers(
+///     values: Vec<v8::Local<'_, v8::Value>>,
+///     scope: &mut v8::PinScope<'_, '_>,
+/// ) -> errors::Result<Vec<u32>> {
+///     values.into_iter().map(|value| from_owned(value, scope)).collect()
+/// }
+/// ```
+///
+/// # Errors
+/// The conversion error when the value cannot be converted
+pub fn from_owned<T>(value: v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<T>
+where
+    T: TryFromValue,
+{
+    T::try_from_value(&value, scope)
+}
+
 impl<T> TryFromValue for Vec<T>
 where
     T: TryFromValue,
 {
-    fn try_from_value(
-        input: &v8::Local<'_, v8::Value>,
-        scope: &mut v8::PinScope<'_, '_>,
-    ) -> errors::Result<Self> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
         try_as_vec(input, scope)
     }
 }
 
-impl<T, S> TryFromValue for HashMap<String, T, S>
+/// Collects the elements of the JS array into the heap, so the order of the array does not matter.
+impl<T> TryFromValue for BinaryHeap<T>
+where
+    T: TryFromValue + Ord,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_vec(input, scope).map(BinaryHeap::from)
+    }
+}
+
+impl<K, T, S> TryFromValue for HashMap<K, T, S>
 where
+    K: TryFromValue + Eq + Hash,
     T: TryFromValue,
     S: BuildHasher + Default,
 {
-    fn try_from_value(
-        input: &v8::Local<'_, v8::Value>,
-        scope: &mut v8::PinScope<'_, '_>,
-    ) -> errors::Result<Self> {
-        try_as_hashmap(input, scope)
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_hashmap_keyed(input, scope)
     }
 }
 
+/// `null` and `undefined` are converted into `None`; any other value is converted into `Some`.
+///
+/// This means an empty array is converted into `Some(vec![])` for an `Option<Vec<T>>`, and is never
+/// collapsed into `None`.
 impl<T> TryFromValue for Option<T>
 where
     T: TryFromValue,
 {
-    fn try_from_value(
-        input: &v8::Local<'_, v8::Value>,
-        scope: &mut v8::PinScope<'_, '_>,
-    ) -> errors::Result<Self> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
         if input.is_null_or_undefined() {
             return Ok(None);
         }
@@ -68,23 +104,113 @@ where
     }
 }
 
+impl<T> TryFromValue for Box<T>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        T::try_from_value(input, scope).map(Box::new)
+    }
+}
+
+impl<T> TryFromValue for Wrapping<T>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        T::try_from_value(input, scope).map(Wrapping)
+    }
+}
+
+impl TryFromValue for Box<str> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_string(input, scope).map(String::into_boxed_str)
+    }
+}
+
+impl<T> TryFromValue for Box<[T]>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_vec(input, scope).map(Vec::into_boxed_slice)
+    }
+}
+
+impl TryFromValue for Arc<str> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_string(input, scope).map(Arc::from)
+    }
+}
+
+/// Reads a `{ start, end }` object; an object with `inclusive: true` is rejected with `Error::Validation`
+impl<T> TryFromValue for Range<T>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        let (start, end) = get_range_bounds(*input, scope, false)?;
+        Ok(start..end)
+    }
+}
+
+/// Reads a `{ start, end, inclusive: true }` object; an object without `inclusive: true` is rejected with
+/// `Error::Validation`
+impl<T> TryFromValue for RangeInclusive<T>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        let (start, end) = get_range_bounds(*input, scope, true)?;
+        Ok(start..=end)
+    }
+}
+
+/// Reads the `start` and `end` of a range object, after checking that its `inclusive` flag matches
+fn get_range_bounds<T>(
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    inclusive: bool,
+) -> errors::Result<(T, T)>
+where
+    T: TryFromValue,
+{
+    let flag = get_optional_field_as("inclusive", &input, scope, try_as_bool)?.unwrap_or(false);
+    if flag != inclusive {
+        let expected = if inclusive { "an inclusive" } else { "an exclusive" };
+        return Err(errors::Error::Validation(format!("expected {expected} range, got inclusive: {flag}")));
+    }
+
+    let start = get_field_as("start", &input, scope, T::try_from_value)?;
+    let end = get_field_as("end", &input, scope, T::try_from_value)?;
+    Ok((start, end))
+}
+
 #[cfg(feature = "json")]
 impl TryFromValue for serde_json::Value {
-    fn try_from_value(
-        input: &v8::Local<'_, v8::Value>,
-        scope: &mut v8::PinScope<'_, '_>,
-    ) -> errors::Result<Self> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
         let value = v8_to_json_value(scope, *input)?;
         Ok(value)
     }
 }
 
+#[cfg(feature = "json")]
+impl TryFromValue for serde_json::Map<String, serde_json::Value> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        if !input.is_object() {
+            return Err(errors::Error::ExpectedObject);
+        }
+
+        v8_object_to_json_map(scope, *input)
+    }
+}
+
 macro_rules! impl_try_from_value {
     ($($t:ty => $func:ident),*) => {
         $(
             impl TryFromValue for $t {
-                fn try_from_value<'a>(
-                    input: &'a v8::Local<'a, v8::Value>,
+                fn try_from_value(
+                    input: &v8::Local<'_, v8::Value>,
                     scope: &mut v8::PinScope<'_, '_>,
                 ) -> errors::Result<Self> {
                     $func(input, scope)
@@ -101,18 +227,62 @@ impl_try_from_value! {
     i32 => try_as_i32,
     i64 => try_as_i64,
     f64 => try_as_f64,
+    u8 => try_as_u8,
+    u16 => try_as_u16,
     u32 => try_as_u32,
-    f32 => try_as_f32
+    u64 => try_as_u64,
+    f32 => try_as_f32,
+    IpAddr => try_as_ip_addr,
+    Ipv4Addr => try_as_ipv4_addr,
+    Ipv6Addr => try_as_ipv6_addr,
+    SocketAddr => try_as_socket_addr,
+    Duration => try_as_duration
+}
+
+/// A tuple reads an array of exactly its length, e.g. `[1, "one"]` for a `(i32, String)`
+macro_rules! impl_try_from_value_for_tuple {
+    ($length:literal => $($index:literal: $t:ident),+) => {
+        impl<$($t),+> TryFromValue for ($($t,)+)
+        where
+            $($t: TryFromValue),+
+        {
+            fn try_from_value(
+                input: &v8::Local<'_, v8::Value>,
+                scope: &mut v8::PinScope<'_, '_>,
+            ) -> errors::Result<Self> {
+                let array = try_as_array_of_length(input, $length)?;
+                Ok(($(get_element_as($index, &array, scope, $t::try_from_value)?,)+))
+            }
+        }
+    };
 }
 
+impl_try_from_value_for_tuple!(1 => 0: A);
+impl_try_from_value_for_tuple!(2 => 0: A, 1: B);
+impl_try_from_value_for_tuple!(3 => 0: A, 1: B, 2: C);
+impl_try_from_value_for_tuple!(4 => 0: A, 1: B, 2: C, 3: D);
+impl_try_from_value_for_tuple!(5 => 0: A, 1: B, 2: C, 3: D, 4: E);
+impl_try_from_value_for_tuple!(6 => 0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+
 #[cfg(test)]
 mod tests {
-    use crate::{self as v8_derive, from::TryFromValue, setup};
-    use std::collections::HashMap;
+    use crate::{
+        self as v8_derive,
+        errors::Error,
+        from::{from_owned, TryFromValue},
+        into::{ExtendObject, IntoObject},
+        setup, IntoValue,
+    };
+    use std::{
+        collections::{BinaryHeap, HashMap},
+        marker::PhantomData,
+        num::Wrapping,
+        sync::Arc,
+    };
     use v8::{ContextOptions, CreateParams, Local, Value};
-    use v8_derive_macros::FromValue;
+    use v8_derive_macros::{FromValue, IntoValue};
 
-    #[derive(Debug, FromValue)]
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
     struct SimpleObject {
         yes_no: bool,
         name: String,
@@ -136,6 +306,295 @@ mod tests {
         vec: Vec<i32>,
     }
 
+    #[derive(FromValue)]
+    struct ObjectWithOptionalVec {
+        vec: Option<Vec<i32>>,
+    }
+
+    #[derive(FromValue)]
+    struct SparseObject {
+        #[v8(skip_holes)]
+        skipped: Vec<i32>,
+        kept: Vec<Option<i32>>,
+    }
+
+    #[derive(Debug, FromValue)]
+    struct IterableObject {
+        #[v8(iterable)]
+        ids: Vec<i32>,
+        #[v8(iterable)]
+        names: Option<Vec<String>>,
+    }
+
+    #[derive(Debug, FromValue)]
+    #[v8(validate = "validate_person")]
+    struct ValidatedPerson {
+        age: i32,
+    }
+
+    fn validate_person(person: &ValidatedPerson) -> Result<(), String> {
+        if person.age < 0 {
+            return Err(format!("age must not be negative, got {}", person.age));
+        }
+        Ok(())
+    }
+
+    #[derive(Debug, FromValue, IntoValue)]
+    struct PathObject {
+        #[v8(path = "meta.id")]
+        id: i32,
+        #[v8(path = "meta.name")]
+        name: Option<String>,
+    }
+
+    #[derive(Debug, FromValue, IntoValue)]
+    #[v8(array)]
+    struct Point {
+        x: i32,
+        y: i32,
+        label: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    struct Rectangle {
+        width: f64,
+        height: f64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    #[v8(discriminant_key = "kind")]
+    enum Shape {
+        Circle { radius: f64 },
+        Rectangle(Rectangle),
+        Empty,
+    }
+
+    #[derive(FromValue, IntoValue)]
+    struct Grid {
+        rows: Vec<Vec<i32>>,
+    }
+
+    #[derive(Debug, FromValue)]
+    struct CoercedObject {
+        #[v8(coerce)]
+        count: i32,
+        #[v8(coerce)]
+        size: Option<u32>,
+    }
+
+    #[derive(FromValue)]
+    struct TruthyObject {
+        enabled: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    struct Node {
+        value: i32,
+        left: Option<Box<Node>>,
+        right: Option<Box<Node>>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, FromValue, IntoValue)]
+    struct UserId(u64);
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Ids(Vec<u64>);
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct User {
+        id: UserId,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromValue, IntoValue)]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(rename_all = "lowercase", case_insensitive)]
+    enum Priority {
+        Low,
+        VeryHigh,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(rename_all = "camelCase")]
+    struct Profile {
+        display_name: String,
+        is_admin: bool,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(rename_all = "PascalCase")]
+    struct Header {
+        content_type: String,
+        max_age: u32,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(discriminant_key = "kind", rename_all = "kebab-case")]
+    enum Shipping {
+        SameDay { rush_fee: u32 },
+        Standard,
+    }
+
+    #[derive(Debug, PartialEq, FromValue)]
+    #[v8(strict_numbers)]
+    struct Ledger {
+        total: i64,
+        count: u64,
+        limit: Option<i64>,
+    }
+
+    fn prefix_key(name: &str) -> String {
+        format!("x_{name}")
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(rename_with = "prefix_key")]
+    struct Prefixed {
+        user_name: String,
+        count: i32,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(transparent)]
+    struct Celsius {
+        value: f64,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(tag = "kind", tag_value = "user")]
+    struct TaggedUser {
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(rename_all = "lowercase")]
+    enum Protocol {
+        Http,
+        Https,
+        #[v8(other)]
+        Other(String),
+    }
+
+    /// A handler without traps, so the proxy forwards every operation to its target
+    fn identity_handler<'s>(scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Object> {
+        v8::Object::new(scope)
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    #[v8(proxy = "identity_handler")]
+    struct Proxied {
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Counters {
+        #[v8(number_if_safe)]
+        small: i64,
+        #[v8(number_if_safe)]
+        huge: i64,
+        #[v8(number_if_safe)]
+        unsigned: Option<u64>,
+    }
+
+    #[derive(Debug, FromValue)]
+    #[v8(partial)]
+    struct Settings {
+        name: String,
+        retries: u8,
+        timeout: Option<u32>,
+    }
+
+    #[derive(Debug, FromValue)]
+    #[v8(partial, tag = "kind", tag_value = "settings")]
+    struct TaggedSettings {
+        name: String,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Word {
+        #[v8(as_string)]
+        letters: Vec<char>,
+        #[v8(as_string)]
+        hint: Option<Vec<char>>,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Account {
+        #[v8(numeric_string)]
+        balance: u64,
+        #[v8(numeric_string)]
+        limit: Option<i64>,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Token {
+        r#type: String,
+    }
+
+    #[derive(Debug, Default, PartialEq, FromValue)]
+    #[v8(default)]
+    struct PartialUpdate {
+        name: String,
+        retries: u32,
+        enabled: bool,
+        tags: Vec<String>,
+        limit: Option<i32>,
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Meters;
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Tagged<T> {
+        id: u32,
+        _unit: PhantomData<T>,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Labeled<T> {
+        label: String,
+        value: T,
+    }
+
+    /// The inferred bound `Vec<Tree<T>>: TryFromValue` would require the impl itself, so it is overridden
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    #[v8(bound(from = "T: v8_derive::TryFromValue", into = "T: v8_derive::IntoValue"))]
+    struct Tree<T> {
+        value: T,
+        children: Vec<Tree<T>>,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Segment {
+        #[v8(fields = ["x", "y"])]
+        start: (i32, i32),
+        #[v8(fields = ["x", "y"])]
+        end: Option<(i32, i32)>,
+        offset: (i32, i32),
+    }
+
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    struct Scores {
+        #[v8(preserve_key_order)]
+        by_id: Vec<(u32, String)>,
+        #[v8(preserve_key_order)]
+        extra: Option<Vec<(String, i32)>>,
+    }
+
+    #[derive(Debug, PartialEq, FromValue, IntoValue)]
+    struct Call {
+        #[v8(numeric_object)]
+        args: (i32, String),
+        #[v8(numeric_object)]
+        extra: Option<(bool,)>,
+    }
+
     #[test]
     fn should_be_able_to_handle_incomplete_values() {
         setup::setup_test();
@@ -416,4 +875,1614 @@ mod tests {
         assert_eq!(s.get("opt"), Some(&"42".to_string()));
         assert_eq!(s.get("avg"), Some(&"42.42".to_string()));
     }
+
+    #[test]
+    fn can_read_fields_from_a_nested_path() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ meta: { id: 42 } })");
+        let p = PathObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.id, 42);
+        assert_eq!(p.name, None);
+
+        // missing leaf
+        let object = setup::eval(scope, "({ meta: {} })");
+        let err = PathObject::try_from_value(&object, scope).expect_err("expected a missing field");
+        assert!(matches!(err, Error::FieldNotFound(path) if path == "meta.id"));
+
+        // missing intermediate object
+        let object = setup::eval(scope, "({ id: 42 })");
+        let err = PathObject::try_from_value(&object, scope).expect_err("expected a missing field");
+        assert!(matches!(err, Error::FieldNotFound(path) if path == "meta.id"));
+    }
+
+    #[test]
+    fn can_write_fields_to_a_nested_path() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let p = PathObject {
+            id: 7,
+            name: Some("Marcel".to_string()),
+        };
+        let object = p.into_value(scope);
+
+        // both fields share the same intermediate object
+        let js_key = v8::String::new(scope, "meta").unwrap().into();
+        let meta = object.to_object(scope).unwrap().get(scope, js_key).unwrap();
+        let meta = HashMap::<String, String>::try_from_value(&meta, scope).expect("expected a meta object");
+        assert_eq!(meta.len(), 2);
+        assert_eq!(meta.get("id"), Some(&"7".to_string()));
+        assert_eq!(meta.get("name"), Some(&"Marcel".to_string()));
+
+        let p = PathObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.id, 7);
+        assert_eq!(p.name.as_deref(), Some("Marcel"));
+    }
+
+    #[test]
+    fn should_distinguish_null_from_an_empty_array() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_array = v8::Array::new(scope, 0).into();
+        let vec = Option::<Vec<i32>>::try_from_value(&js_array, scope).unwrap();
+        assert_eq!(vec, Some(vec![]));
+
+        let js_null = v8::null(scope).into();
+        let vec = Option::<Vec<i32>>::try_from_value(&js_null, scope).unwrap();
+        assert_eq!(vec, None);
+
+        // as a field of a derived struct
+        let object = setup::eval(scope, "({ vec: [] })");
+        let p = ObjectWithOptionalVec::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.vec, Some(vec![]));
+
+        let object = setup::eval(scope, "({ vec: null })");
+        let p = ObjectWithOptionalVec::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.vec, None);
+
+        let object = setup::eval(scope, "({})");
+        let p = ObjectWithOptionalVec::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.vec, None);
+
+        // and back
+        let js_value = Some(Vec::<i32>::new()).into_value(scope);
+        assert!(js_value.is_array());
+        let js_value = None::<Vec<i32>>.into_value(scope);
+        assert!(js_value.is_null());
+    }
+
+    #[test]
+    fn should_run_the_validator_after_deserializing() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ age: 42 })");
+        let person = ValidatedPerson::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(person.age, 42);
+
+        // a valid integer, rejected by the validator
+        let object = setup::eval(scope, "({ age: -1 })");
+        let err = ValidatedPerson::try_from_value(&object, scope).expect_err("expected a validation error");
+        assert!(matches!(err, Error::Validation(message) if message == "age must not be negative, got -1"));
+    }
+
+    #[test]
+    fn can_convert_deeply_nested_values_with_a_single_scope() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(
+            scope,
+            r#"[{
+                "first": [{ nested: { yes_no: true, name: "Marcel", age: 30, opt: null, avg: 1.5 } }],
+                "second": null
+            }]"#,
+        );
+
+        // the same scope flows through the trait, the helpers and the derived implementations
+        let nested = Vec::<HashMap<String, Option<Vec<ParentObject>>>>::try_from_value(&object, scope)
+            .expect("failed to deserialize");
+        assert_eq!(nested.len(), 1);
+        let first = nested[0].get("first").unwrap().as_ref().unwrap();
+        assert_eq!(first[0].nested.name, "Marcel");
+        assert_eq!(first[0].nested.opt, None);
+        assert!(nested[0].get("second").unwrap().is_none());
+
+        let nested = v8_derive::helpers::try_as_vec::<HashMap<String, Option<Vec<ParentObject>>>>(&object, scope)
+            .expect("failed to deserialize");
+        assert_eq!(nested.len(), 1);
+    }
+
+    #[test]
+    fn can_handle_holes_in_sparse_arrays() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ skipped: [1, , 3], kept: [1, , 3] })");
+        let p = SparseObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.skipped, vec![1, 3]);
+        assert_eq!(p.kept, vec![Some(1), None, Some(3)]);
+
+        // an explicit undefined is not a hole
+        let array = setup::eval(scope, "[1, undefined, 3]");
+        let vec = v8_derive::helpers::try_as_vec_skip_holes::<Option<i32>>(&array, scope).unwrap();
+        assert_eq!(vec, vec![Some(1), None, Some(3)]);
+    }
+
+    #[test]
+    fn can_convert_structs_as_positional_arrays() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let p = Point {
+            x: 1,
+            y: 2,
+            label: Some("origin".to_string()),
+        };
+        let array = p.into_value(scope);
+        assert!(array.is_array());
+
+        let values = Vec::<String>::try_from_value(&array, scope).unwrap();
+        assert_eq!(values, vec!["1", "2", "origin"]);
+
+        let p = Point::try_from_value(&array, scope).expect("failed to deserialize");
+        assert_eq!((p.x, p.y), (1, 2));
+        assert_eq!(p.label.as_deref(), Some("origin"));
+
+        let array = setup::eval(scope, "[3, 4, null]");
+        let p = Point::try_from_value(&array, scope).expect("failed to deserialize");
+        assert_eq!((p.x, p.y, p.label), (3, 4, None));
+
+        let array = setup::eval(scope, "[3, 4]");
+        let err = Point::try_from_value(&array, scope).expect_err("expected a length mismatch");
+        assert!(matches!(err, Error::WrongArrayLength { expected: 3, actual: 2 }));
+
+        let object = setup::eval(scope, "({ x: 3, y: 4, label: null })");
+        let err = Point::try_from_value(&object, scope).expect_err("expected an array");
+        assert!(matches!(err, Error::ExpectedArray));
+    }
+
+    #[test]
+    fn can_convert_enums_by_discriminant_key() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ kind: 'Circle', radius: 1.5 })");
+        let shape = Shape::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(shape, Shape::Circle { radius: 1.5 });
+
+        let object = setup::eval(scope, "({ kind: 'Rectangle', width: 2, height: 3 })");
+        let shape = Shape::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(
+            shape,
+            Shape::Rectangle(Rectangle {
+                width: 2.0,
+                height: 3.0
+            })
+        );
+
+        let object = setup::eval(scope, "({ kind: 'Empty' })");
+        let shape = Shape::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(shape, Shape::Empty);
+
+        let object = setup::eval(scope, "({ kind: 'Triangle' })");
+        let err = Shape::try_from_value(&object, scope).expect_err("expected an unknown variant");
+        assert!(matches!(err, Error::UnknownVariant(variant) if variant == "Triangle"));
+
+        // the discriminant key is written next to the fields
+        for (shape, name) in [
+            (Shape::Circle { radius: 0.5 }, "Circle"),
+            (
+                Shape::Rectangle(Rectangle {
+                    width: 4.0,
+                    height: 1.0,
+                }),
+                "Rectangle",
+            ),
+            (Shape::Empty, "Empty"),
+        ] {
+            let object = shape.clone().into_value(scope);
+            let js_key = v8::String::new(scope, "kind").unwrap().into();
+            let kind = object.to_object(scope).unwrap().get(scope, js_key).unwrap();
+            assert_eq!(kind.to_rust_string_lossy(scope), name);
+
+            let result = Shape::try_from_value(&object, scope).expect("failed to deserialize");
+            assert_eq!(result, shape);
+        }
+    }
+
+    #[test]
+    fn can_convert_jagged_nested_arrays() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let array = setup::eval(scope, "[[1, 2, 3], [], [4]]");
+        let rows = Vec::<Vec<i32>>::try_from_value(&array, scope).expect("failed to deserialize");
+        assert_eq!(rows, vec![vec![1, 2, 3], vec![], vec![4]]);
+
+        let rows = vec![vec![], vec![5], vec![6, 7]];
+        let array = rows.clone().into_value(scope);
+        let result = Vec::<Vec<i32>>::try_from_value(&array, scope).expect("failed to deserialize");
+        assert_eq!(result, rows);
+
+        let deep = vec![vec![vec![1], vec![]], vec![]];
+        let array = deep.clone().into_value(scope);
+        let result = Vec::<Vec<Vec<i32>>>::try_from_value(&array, scope).expect("failed to deserialize");
+        assert_eq!(result, deep);
+
+        // as a field of a derived struct
+        let grid = Grid {
+            rows: vec![vec![1, 2], vec![]],
+        };
+        let object = grid.into_value(scope);
+        let grid = Grid::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(grid.rows, vec![vec![1, 2], vec![]]);
+
+        // an inner value that is not an array
+        let array = setup::eval(scope, "[[1], 2]");
+        let err = Vec::<Vec<i32>>::try_from_value(&array, scope).expect_err("expected an array");
+        assert!(matches!(err, Error::ExpectedArray));
+    }
+
+    #[test]
+    fn should_quote_the_offending_value_in_errors() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "12n");
+        let err = i32::try_from_value(&value, scope).expect_err("expected an i32 error");
+        assert!(matches!(&err, Error::WithPreview { preview, .. } if preview == "12n"));
+        assert!(matches!(err.without_preview(), Error::ExpectedI32));
+        assert_eq!(err.to_string(), "Conversion failed; Expected Int32; got 12n");
+
+        // objects are not converted to a string
+        let value = setup::eval(scope, "({ toString() { throw new Error('side effect'); } })");
+        let err = i64::try_from_value(&value, scope).expect_err("expected an i64 error");
+        assert!(matches!(&err, Error::WithPreview { preview, .. } if preview == "object"));
+
+        // long strings are truncated
+        let value = setup::eval(scope, "'x'.repeat(100)");
+        let err = i64::try_from_value(&value, scope).expect_err("expected an i64 error");
+        let expected = format!("\"{}…\"", "x".repeat(64));
+        assert!(matches!(&err, Error::WithPreview { preview, .. } if *preview == expected));
+    }
+
+    #[test]
+    fn can_coerce_big_ints_into_small_integers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ count: 5n, size: 7n })");
+        let p = CoercedObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.count, 5);
+        assert_eq!(p.size, Some(7));
+
+        // numbers are still accepted
+        let object = setup::eval(scope, "({ count: -5 })");
+        let p = CoercedObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.count, -5);
+        assert_eq!(p.size, None);
+
+        let object = setup::eval(scope, "({ count: 2n ** 40n })");
+        let err = CoercedObject::try_from_value(&object, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+
+        let object = setup::eval(scope, "({ count: 1n, size: -1n })");
+        let err = CoercedObject::try_from_value(&object, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+
+        let object = setup::eval(scope, "({ count: 2n ** 64n })");
+        let err = CoercedObject::try_from_value(&object, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+    }
+
+    #[test]
+    fn can_convert_any_value_into_a_bool_field() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // a bool field follows JS truthiness
+        for (source, expected) in [
+            ("({ enabled: 0 })", false),
+            ("({ enabled: '' })", false),
+            ("({ enabled: NaN })", false),
+            ("({})", false),
+            ("({ enabled: 1 })", true),
+            ("({ enabled: 'x' })", true),
+            ("({ enabled: [] })", true),
+        ] {
+            let object = setup::eval(scope, source);
+            let p = TruthyObject::try_from_value(&object, scope).expect("failed to deserialize");
+            assert_eq!(p.enabled, expected, "{source}");
+        }
+    }
+
+    #[test]
+    fn can_convert_recursive_boxed_structs() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let leaf = |value| Node {
+            value,
+            left: None,
+            right: None,
+        };
+        let tree = Node {
+            value: 1,
+            left: Some(Box::new(Node {
+                value: 2,
+                left: Some(Box::new(leaf(4))),
+                right: None,
+            })),
+            right: Some(Box::new(leaf(3))),
+        };
+
+        let object = tree.clone().into_value(scope);
+        let result = Node::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(result, tree);
+
+        let object = setup::eval(scope, "({ value: 1, right: { value: 3, left: { value: 6 } } })");
+        let result = Node::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(result.left, None);
+        let right = result.right.expect("expected a right child");
+        assert_eq!(right.value, 3);
+        assert_eq!(right.left.map(|node| node.value), Some(6));
+    }
+
+    #[test]
+    fn should_fail_on_an_unreadable_array_element() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let array = setup::eval(
+            scope,
+            "Object.defineProperty([1, 2, 3], 1, { get() { throw new Error('unreadable'); } })",
+        );
+        assert!(array.is_array());
+
+        v8::tc_scope!(let tc, scope);
+        let err = Vec::<i32>::try_from_value(&array, tc).expect_err("expected a missing element");
+        assert!(matches!(err, Error::MissingArrayElement { index: 1 }));
+        assert!(tc.has_caught());
+    }
+
+    #[test]
+    fn can_convert_newtypes_transparently() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = UserId(7).into_value(scope);
+        assert!(value.is_big_int());
+        assert_eq!(value.to_rust_string_lossy(scope), "7");
+        assert_eq!(UserId::try_from_value(&value, scope).unwrap(), UserId(7));
+
+        // as a field of a derived struct
+        let object = setup::eval(scope, "({ id: 42n, name: 'Marcel' })");
+        let user = User::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(user.id, UserId(42));
+
+        let object = user.into_value(scope);
+        let js_key = v8::String::new(scope, "id").unwrap().into();
+        let id = object.to_object(scope).unwrap().get(scope, js_key).unwrap();
+        assert!(id.is_big_int());
+
+        let value = setup::eval(scope, "-1n");
+        let err = UserId::try_from_value(&value, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+    }
+
+    #[test]
+    fn can_convert_ranges() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = (0..100).into_value(scope);
+        let map = HashMap::<String, String>::try_from_value(&value, scope).unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("start").map(String::as_str), Some("0"));
+        assert_eq!(map.get("end").map(String::as_str), Some("100"));
+        assert_eq!(std::ops::Range::<i32>::try_from_value(&value, scope).unwrap(), 0..100);
+
+        let value = (0..=100).into_value(scope);
+        let map = HashMap::<String, String>::try_from_value(&value, scope).unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get("inclusive").map(String::as_str), Some("true"));
+        assert_eq!(
+            std::ops::RangeInclusive::<i32>::try_from_value(&value, scope).unwrap(),
+            0..=100
+        );
+
+        let value = setup::eval(scope, "'0..100'");
+        let err = std::ops::Range::<i32>::try_from_value(&value, scope).expect_err("expected an object");
+        assert!(matches!(err, Error::ExpectedObject));
+
+        // an exclusive range does not read back as an inclusive one, and the reverse
+        let value = (0..100).into_value(scope);
+        let err = std::ops::RangeInclusive::<i32>::try_from_value(&value, scope).expect_err("expected a mismatch");
+        assert!(
+            matches!(err, Error::Validation(message) if message == "expected an inclusive range, got inclusive: false")
+        );
+
+        let value = (0..=100).into_value(scope);
+        let err = std::ops::Range::<i32>::try_from_value(&value, scope).expect_err("expected a mismatch");
+        assert!(
+            matches!(err, Error::Validation(message) if message == "expected an exclusive range, got inclusive: true")
+        );
+    }
+
+    #[test]
+    fn can_convert_maps_with_enum_keys() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Color::Green.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "Green");
+        assert_eq!(Color::try_from_value(&value, scope).unwrap(), Color::Green);
+
+        let map = HashMap::from([(Color::Red, 1), (Color::Blue, 3)]);
+
+        // as an object keyed by the variant names
+        let object = map.clone().into_object(scope);
+        let names = HashMap::<String, i32>::try_from_value(&object, scope).unwrap();
+        assert_eq!(names, HashMap::from([("Red".to_string(), 1), ("Blue".to_string(), 3)]));
+        assert_eq!(HashMap::<Color, i32>::try_from_value(&object, scope).unwrap(), map);
+
+        // as a JS Map
+        let js_map = map.clone().into_value(scope);
+        assert!(js_map.is_map());
+        assert_eq!(HashMap::<Color, i32>::try_from_value(&js_map, scope).unwrap(), map);
+
+        let object = setup::eval(scope, "({ Purple: 1 })");
+        let err = HashMap::<Color, i32>::try_from_value(&object, scope).expect_err("expected an unknown variant");
+        assert!(matches!(err, Error::UnknownVariant(variant) if variant == "Purple"));
+    }
+
+    #[test]
+    fn can_convert_numeric_strings() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let account = Account {
+            balance: u64::MAX,
+            limit: Some(-5),
+        };
+        let value = account.into_value(scope);
+        let object = value.to_object(scope).unwrap();
+        let key = v8::String::new(scope, "balance").unwrap().into();
+        let balance = object.get(scope, key).unwrap();
+        assert!(balance.is_string());
+        assert_eq!(balance.to_rust_string_lossy(scope), "18446744073709551615");
+
+        let account = Account::try_from_value(&value, scope).expect("expected an account");
+        assert_eq!(
+            account,
+            Account {
+                balance: u64::MAX,
+                limit: Some(-5)
+            }
+        );
+
+        let value = setup::eval(scope, "({ balance: '42' })");
+        let account = Account::try_from_value(&value, scope).expect("expected an account");
+        assert_eq!(
+            account,
+            Account {
+                balance: 42,
+                limit: None
+            }
+        );
+
+        let value = setup::eval(scope, "({ balance: 'abc' })");
+        let err = Account::try_from_value(&value, scope).expect_err("expected an invalid number");
+        assert!(matches!(err, Error::InvalidNumber(number) if number == "abc"));
+
+        let value = setup::eval(scope, "({ balance: 42 })");
+        let err = Account::try_from_value(&value, scope).expect_err("expected a string");
+        assert!(matches!(err, Error::ExpectedString));
+    }
+
+    #[test]
+    fn can_convert_raw_identifiers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let token = Token {
+            r#type: "keyword".to_string(),
+        };
+        let value = token.into_value(scope);
+        let object = value.to_object(scope).unwrap();
+        let key = v8::String::new(scope, "type").unwrap().into();
+        let kind = object.get(scope, key).unwrap();
+        assert_eq!(kind.to_rust_string_lossy(scope), "keyword");
+
+        let token = Token::try_from_value(&value, scope).unwrap();
+        assert_eq!(token.r#type, "keyword");
+
+        let value = setup::eval(scope, "({ type: 'ident' })");
+        let token = Token::try_from_value(&value, scope).unwrap();
+        assert_eq!(token.r#type, "ident");
+    }
+
+    #[test]
+    fn can_convert_maps_of_derived_structs() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let john = SimpleObject {
+            yes_no: true,
+            name: "John".to_string(),
+            age: 42,
+            opt: None,
+            avg: 1.5,
+        };
+        let map = HashMap::from([("john".to_string(), john.clone())]);
+
+        // `into_value` creates a JS Map, `into_object` a plain object; both convert back
+        let js_map = map.clone().into_value(scope);
+        assert!(js_map.is_map());
+        assert_eq!(
+            HashMap::<String, SimpleObject>::try_from_value(&js_map, scope).unwrap(),
+            map
+        );
+
+        let object = map.clone().into_object(scope);
+        assert!(object.is_object() && !object.is_map());
+        assert_eq!(
+            HashMap::<String, SimpleObject>::try_from_value(&object, scope).unwrap(),
+            map
+        );
+
+        let object = setup::eval(
+            scope,
+            "({ john: { yes_no: true, name: 'John', age: 42, avg: 1.5 }, jane: { yes_no: false, name: 'Jane', age: 37, opt: 1, avg: 2 } })",
+        );
+        let people = HashMap::<String, SimpleObject>::try_from_value(&object, scope).unwrap();
+        assert_eq!(people.len(), 2);
+        assert_eq!(people["john"], john);
+        assert_eq!(people["jane"].opt, Some(1));
+    }
+
+    #[test]
+    fn can_default_missing_fields() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({})");
+        let update = PartialUpdate::try_from_value(&value, scope).unwrap();
+        assert_eq!(update, PartialUpdate::default());
+
+        let value = setup::eval(scope, "({ retries: 3, tags: ['a'] })");
+        let update = PartialUpdate::try_from_value(&value, scope).unwrap();
+        assert_eq!(
+            update,
+            PartialUpdate {
+                retries: 3,
+                tags: vec!["a".to_string()],
+                ..PartialUpdate::default()
+            }
+        );
+
+        // a present field of the wrong type is still an error
+        let value = setup::eval(scope, "({ tags: 'a' })");
+        let err = PartialUpdate::try_from_value(&value, scope).expect_err("expected an array");
+        assert!(matches!(err.without_preview(), Error::ExpectedArray));
+    }
+
+    #[test]
+    fn can_convert_shared_strings() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let shared: Arc<str> = Arc::from("shared ✓");
+        let value = Arc::clone(&shared).into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(Arc::<str>::try_from_value(&value, scope).unwrap(), shared);
+
+        let boxed: Box<str> = "boxed ✓".into();
+        let value = boxed.clone().into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(Box::<str>::try_from_value(&value, scope).unwrap(), boxed);
+    }
+
+    #[test]
+    fn can_coerce_number_objects_into_small_integers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(scope, "({ count: new Number(5), size: new Number(7) })");
+        let p = CoercedObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.count, 5);
+        assert_eq!(p.size, Some(7));
+
+        let object = setup::eval(scope, "({ count: Object(5n), size: Object(7n) })");
+        let p = CoercedObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.count, 5);
+        assert_eq!(p.size, Some(7));
+
+        // without the attribute a wrapper object is not a u32
+        let wrapper = setup::eval(scope, "new Number(7)");
+        assert!(v8_derive::helpers::try_as_u32(&wrapper, scope).is_err());
+    }
+
+    #[test]
+    fn can_convert_generic_structs() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // the marker is left out, and its type does not need to be convertible
+        let tagged = Tagged::<Meters> {
+            id: 5,
+            _unit: PhantomData,
+        };
+        let value = tagged.into_value(scope);
+        let names = value
+            .to_object(scope)
+            .unwrap()
+            .get_own_property_names(scope, v8::GetPropertyNamesArgs::default())
+            .unwrap();
+        assert_eq!(names.length(), 1);
+        assert_eq!(
+            Tagged::<Meters>::try_from_value(&value, scope).unwrap(),
+            Tagged {
+                id: 5,
+                _unit: PhantomData
+            }
+        );
+
+        let labeled = Labeled {
+            label: "answer".to_string(),
+            value: 42,
+        };
+        let value = labeled.into_value(scope);
+        let labeled = Labeled::<i32>::try_from_value(&value, scope).unwrap();
+        assert_eq!(labeled.value, 42);
+    }
+
+    #[test]
+    fn can_convert_tuples() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = (1, "one".to_string()).into_value(scope);
+        assert!(value.is_array());
+        assert_eq!(
+            <(i32, String)>::try_from_value(&value, scope).unwrap(),
+            (1, "one".to_string())
+        );
+
+        let value = setup::eval(scope, "[1, 2, 3]");
+        let err = <(i32, i32)>::try_from_value(&value, scope).expect_err("expected a wrong length");
+        assert!(matches!(err, Error::WrongArrayLength { expected: 2, actual: 3 }));
+
+        let segment = Segment {
+            start: (1, 2),
+            end: Some((3, 4)),
+            offset: (5, 6),
+        };
+        let value = segment.into_value(scope);
+        let js_key = v8::String::new(scope, "segment").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(segment)");
+        assert_eq!(
+            json.to_rust_string_lossy(scope),
+            r#"{"start":{"x":1,"y":2},"end":{"x":3,"y":4},"offset":[5,6]}"#
+        );
+        assert_eq!(
+            Segment::try_from_value(&value, scope).unwrap(),
+            Segment {
+                start: (1, 2),
+                end: Some((3, 4)),
+                offset: (5, 6),
+            }
+        );
+
+        let value = setup::eval(scope, "({ start: { x: -1, y: -2 }, offset: [0, 0] })");
+        let segment = Segment::try_from_value(&value, scope).unwrap();
+        assert_eq!(segment.start, (-1, -2));
+        assert_eq!(segment.end, None);
+    }
+
+    #[test]
+    fn can_rename_and_match_variants_ignoring_case() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Priority::VeryHigh.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "veryhigh");
+
+        for name in ["'veryhigh'", "'VeryHigh'", "'VERYHIGH'", "'veryHigh'"] {
+            let value = setup::eval(scope, name);
+            assert_eq!(Priority::try_from_value(&value, scope).unwrap(), Priority::VeryHigh);
+        }
+
+        let value = setup::eval(scope, "'LOW'");
+        assert_eq!(Priority::try_from_value(&value, scope).unwrap(), Priority::Low);
+
+        let value = setup::eval(scope, "'very_high'");
+        let err = Priority::try_from_value(&value, scope).expect_err("expected an unknown variant");
+        assert!(matches!(err, Error::UnknownVariant(variant) if variant == "very_high"));
+
+        // without case_insensitive the renamed variant must match exactly
+        let value = Shipping::SameDay { rush_fee: 5 }.into_value(scope);
+        let object = value.to_object(scope).unwrap();
+        let kind = v8::String::new(scope, "kind").unwrap().into();
+        let kind = object.get(scope, kind).unwrap();
+        assert_eq!(kind.to_rust_string_lossy(scope), "same-day");
+        // the fields of a variant keep their names
+        let rush_fee = v8::String::new(scope, "rush_fee").unwrap().into();
+        assert!(object.has(scope, rush_fee).unwrap());
+        assert_eq!(
+            Shipping::try_from_value(&value, scope).unwrap(),
+            Shipping::SameDay { rush_fee: 5 }
+        );
+
+        let value = setup::eval(scope, "({ kind: 'Standard' })");
+        let err = Shipping::try_from_value(&value, scope).expect_err("expected an unknown variant");
+        assert!(matches!(err, Error::UnknownVariant(variant) if variant == "Standard"));
+    }
+
+    #[test]
+    fn can_rename_all_fields() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let profile = Profile {
+            display_name: "Ada".to_string(),
+            is_admin: true,
+        };
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        let value = profile.into_value(scope);
+        context.global(scope).set(scope, js_key, value);
+        let keys = setup::eval(scope, "Object.keys(value).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "displayName,isAdmin");
+
+        let value = setup::eval(scope, "({ displayName: 'Grace', isAdmin: false })");
+        assert_eq!(
+            Profile::try_from_value(&value, scope).unwrap(),
+            Profile {
+                display_name: "Grace".to_string(),
+                is_admin: false,
+            }
+        );
+    }
+
+    #[test]
+    fn can_rename_all_fields_to_pascal_case() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let header = Header {
+            content_type: "text/plain".to_string(),
+            max_age: 60,
+        };
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        let value = header.into_value(scope);
+        context.global(scope).set(scope, js_key, value);
+        let keys = setup::eval(scope, "Object.keys(value).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "ContentType,MaxAge");
+
+        let value = setup::eval(scope, "({ ContentType: 'text/html', MaxAge: 0 })");
+        assert_eq!(
+            Header::try_from_value(&value, scope).unwrap(),
+            Header {
+                content_type: "text/html".to_string(),
+                max_age: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn can_convert_wrapping_integers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let counter = Wrapping(u32::MAX);
+        let value = counter.into_value(scope);
+        assert_eq!(value.uint32_value(scope), Some(u32::MAX));
+        assert_eq!(Wrapping::<u32>::try_from_value(&value, scope).unwrap(), counter);
+
+        let value = (counter + Wrapping(2)).into_value(scope);
+        assert_eq!(Wrapping::<u32>::try_from_value(&value, scope).unwrap(), Wrapping(1));
+    }
+
+    #[test]
+    fn can_override_the_inferred_bounds() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let tree = Tree {
+            value: 1,
+            children: vec![Tree {
+                value: 2,
+                children: vec![],
+            }],
+        };
+        let value = tree.clone().into_value(scope);
+        assert_eq!(Tree::<i32>::try_from_value(&value, scope).unwrap(), tree);
+
+        let value = setup::eval(
+            scope,
+            "({ value: 'root', children: [{ value: 'leaf', children: [] }] })",
+        );
+        let tree = Tree::<String>::try_from_value(&value, scope).unwrap();
+        assert_eq!(tree.children[0].value, "leaf");
+    }
+
+    #[test]
+    fn can_convert_a_method_call_tuple_with_a_derived_struct() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // a JSON-RPC style `[methodName, params]` payload
+        let value = setup::eval(
+            scope,
+            "['update', { yes_no: true, name: 'params', age: 3, opt: null, avg: 0.5 }]",
+        );
+        let (method, params) = <(String, SimpleObject)>::try_from_value(&value, scope).unwrap();
+        assert_eq!(method, "update");
+        assert_eq!(
+            params,
+            SimpleObject {
+                yes_no: true,
+                name: "params".to_string(),
+                age: 3,
+                opt: None,
+                avg: 0.5,
+            }
+        );
+
+        let value = (method, params.clone()).into_value(scope);
+        assert_eq!(
+            <(String, SimpleObject)>::try_from_value(&value, scope).unwrap(),
+            ("update".to_string(), params)
+        );
+
+        let value = setup::eval(scope, "['update', 5]");
+        let err = <(String, SimpleObject)>::try_from_value(&value, scope).expect_err("expected an object");
+        assert!(matches!(err, Error::ExpectedObject));
+    }
+
+    #[test]
+    fn should_reject_lossy_numbers_with_strict_numbers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // numbers that hold an integer exactly are fine, and BigInts always are
+        let value = setup::eval(scope, "({ total: -9007199254740991, count: 2n ** 64n - 1n, limit: 5 })");
+        assert_eq!(
+            Ledger::try_from_value(&value, scope).unwrap(),
+            Ledger {
+                total: -9_007_199_254_740_991,
+                count: u64::MAX,
+                limit: Some(5),
+            }
+        );
+
+        // beyond Number.MAX_SAFE_INTEGER the double is already rounded
+        let value = setup::eval(scope, "({ total: 9007199254740993, count: 1 })");
+        let err = Ledger::try_from_value(&value, scope).expect_err("expected a lossy conversion");
+        assert!(matches!(err.without_preview(), Error::LossyConversion));
+
+        let value = setup::eval(scope, "({ total: 1, count: 1.5 })");
+        let err = Ledger::try_from_value(&value, scope).expect_err("expected a lossy conversion");
+        assert!(matches!(err.without_preview(), Error::LossyConversion));
+
+        let value = setup::eval(scope, "({ total: 1, count: -1 })");
+        let err = Ledger::try_from_value(&value, scope).expect_err("expected a negative count");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+    }
+
+    #[test]
+    fn should_read_a_map_and_an_object_into_the_same_hashmap() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let expected = HashMap::from([("one".to_string(), 1), ("2".to_string(), 2)]);
+
+        // the entries of a Map are read, not its own properties
+        let value = setup::eval(scope, "const map = new Map([['one', 1], [2, 2]]); map.extra = 3; map");
+        assert_eq!(HashMap::<String, i32>::try_from_value(&value, scope).unwrap(), expected);
+
+        let value = setup::eval(scope, "({ one: 1, 2: 2 })");
+        assert_eq!(HashMap::<String, i32>::try_from_value(&value, scope).unwrap(), expected);
+    }
+
+    #[test]
+    fn can_rename_fields_with_a_function() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let prefixed = Prefixed {
+            user_name: "Ada".to_string(),
+            count: 3,
+        };
+        let value = prefixed.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let keys = setup::eval(scope, "Object.keys(value).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "x_user_name,x_count");
+
+        let value = setup::eval(scope, "({ x_user_name: 'Grace', x_count: 7 })");
+        assert_eq!(
+            Prefixed::try_from_value(&value, scope).unwrap(),
+            Prefixed {
+                user_name: "Grace".to_string(),
+                count: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn can_convert_a_transparent_struct() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Celsius { value: 21.5 }.into_value(scope);
+        assert!(value.is_number());
+        assert_eq!(value.number_value(scope), Some(21.5));
+        assert_eq!(Celsius::try_from_value(&value, scope).unwrap(), Celsius { value: 21.5 });
+
+        let value = setup::eval(scope, "-40");
+        assert_eq!(Celsius::try_from_value(&value, scope).unwrap(), Celsius { value: -40.0 });
+    }
+
+    #[test]
+    fn can_convert_a_binary_heap() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let heap = BinaryHeap::from(vec![3, 1, 4, 1, 5]);
+        let value = heap.into_value(scope);
+        assert!(value.is_array());
+
+        // the array is in heap order, so only compare the elements
+        let mut elements = Vec::<i32>::try_from_value(&value, scope).unwrap();
+        elements.sort_unstable();
+        assert_eq!(elements, vec![1, 1, 3, 4, 5]);
+
+        let heap = BinaryHeap::<i32>::try_from_value(&value, scope).unwrap();
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 3, 4, 5]);
+
+        let value = setup::eval(scope, "[2, 9, 7]");
+        let mut heap = BinaryHeap::<i32>::try_from_value(&value, scope).unwrap();
+        assert_eq!(heap.pop(), Some(9));
+    }
+
+    #[test]
+    fn should_write_and_check_the_tag_of_a_struct() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let user = TaggedUser {
+            name: "Ada".to_string(),
+        };
+        let value = user.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(json.to_rust_string_lossy(scope), r#"{"kind":"user","name":"Ada"}"#);
+        assert_eq!(
+            TaggedUser::try_from_value(&value, scope).unwrap(),
+            TaggedUser {
+                name: "Ada".to_string()
+            }
+        );
+
+        let value = setup::eval(scope, "({ kind: 'admin', name: 'Grace' })");
+        let err = TaggedUser::try_from_value(&value, scope).expect_err("expected a tag mismatch");
+        assert!(
+            matches!(err, Error::TagMismatch { expected, actual } if expected == "user" && actual == "admin")
+        );
+
+        let value = setup::eval(scope, "({ name: 'Grace' })");
+        let err = TaggedUser::try_from_value(&value, scope).expect_err("expected a tag mismatch");
+        assert!(matches!(err, Error::TagMismatch { actual, .. } if actual == "undefined"));
+    }
+
+    #[test]
+    fn can_write_a_tagged_struct_into_an_existing_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = v8::Object::new(scope);
+        TaggedUser {
+            name: "Ada".to_string(),
+        }
+        .write_into(object, scope);
+
+        let value: Local<Value> = object.into();
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(json.to_rust_string_lossy(scope), r#"{"kind":"user","name":"Ada"}"#);
+        assert_eq!(
+            TaggedUser::try_from_value(&value, scope).unwrap(),
+            TaggedUser {
+                name: "Ada".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn can_convert_a_vec_of_references() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let people: Vec<SimpleObject> = [("Ann", 17), ("Bob", 34), ("Cid", 52)]
+            .into_iter()
+            .map(|(name, age)| SimpleObject {
+                yes_no: true,
+                name: name.to_string(),
+                age,
+                opt: None,
+                avg: 0.5,
+            })
+            .collect();
+        let adults: Vec<&SimpleObject> = people.iter().filter(|person| person.age >= 18).collect();
+
+        let value = adults.into_value(scope);
+        let converted = Vec::<SimpleObject>::try_from_value(&value, scope).unwrap();
+        assert_eq!(converted, people[1..]);
+    }
+
+    #[test]
+    fn can_convert_newtypes_over_collections() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Ids(vec![1, 2, 3]).into_value(scope);
+        assert!(value.is_array());
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let joined = setup::eval(scope, "value.join()");
+        assert_eq!(joined.to_rust_string_lossy(scope), "1,2,3");
+        assert_eq!(Ids::try_from_value(&value, scope).unwrap(), Ids(vec![1, 2, 3]));
+
+        let value = setup::eval(scope, "[10n, 20n]");
+        assert_eq!(Ids::try_from_value(&value, scope).unwrap(), Ids(vec![10, 20]));
+    }
+
+    #[test]
+    fn should_stringify_the_values_of_a_string_hashmap() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = setup::eval(
+            scope,
+            "({ age: 30, ok: true, none: null, list: [1, 2], nested: { a: 1 }, big: 10n })",
+        );
+        let map = HashMap::<String, String>::try_from_value(&object, scope).unwrap();
+        assert_eq!(map["age"], "30");
+        assert_eq!(map["ok"], "true");
+        assert_eq!(map["none"], "null");
+        assert_eq!(map["list"], "1,2");
+        // a nested object is not converted into JSON
+        assert_eq!(map["nested"], "[object Object]");
+        assert_eq!(map["big"], "10");
+    }
+
+    #[test]
+    fn should_keep_an_unknown_variant_in_the_other_variant() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'https'");
+        assert_eq!(Protocol::try_from_value(&value, scope).unwrap(), Protocol::Https);
+
+        let value = setup::eval(scope, "'gopher'");
+        assert_eq!(
+            Protocol::try_from_value(&value, scope).unwrap(),
+            Protocol::Other("gopher".to_string())
+        );
+
+        // the name of the other variant is not a known name
+        let value = setup::eval(scope, "'other'");
+        assert_eq!(
+            Protocol::try_from_value(&value, scope).unwrap(),
+            Protocol::Other("other".to_string())
+        );
+
+        let value = Protocol::Other("gopher".to_string()).into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "gopher");
+        let value = Protocol::Http.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "http");
+    }
+
+    #[test]
+    fn can_wrap_a_struct_in_a_proxy() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Proxied {
+            name: "Ada".to_string(),
+        }
+        .into_value(scope);
+        assert!(value.is_proxy());
+
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let name = setup::eval(scope, "value.name");
+        assert_eq!(name.to_rust_string_lossy(scope), "Ada");
+
+        assert_eq!(
+            Proxied::try_from_value(&value, scope).unwrap(),
+            Proxied {
+                name: "Ada".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn should_convert_safe_integers_into_numbers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let counters = Counters {
+            small: -42,
+            huge: i64::MIN,
+            unsigned: Some(9_007_199_254_740_992),
+        };
+        let value = counters.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let types = setup::eval(scope, "[typeof value.small, typeof value.huge, typeof value.unsigned].join()");
+        assert_eq!(types.to_rust_string_lossy(scope), "number,bigint,bigint");
+        assert_eq!(
+            Counters::try_from_value(&value, scope).unwrap(),
+            Counters {
+                small: -42,
+                huge: i64::MIN,
+                unsigned: Some(9_007_199_254_740_992),
+            }
+        );
+
+        // Number.MAX_SAFE_INTEGER is the largest number
+        let value = Counters {
+            small: 9_007_199_254_740_991,
+            huge: -9_007_199_254_740_991,
+            unsigned: Some(9_007_199_254_740_991),
+        }
+        .into_value(scope);
+        context.global(scope).set(scope, js_key, value);
+        let types = setup::eval(scope, "[typeof value.small, typeof value.huge, typeof value.unsigned].join()");
+        assert_eq!(types.to_rust_string_lossy(scope), "number,number,number");
+    }
+
+    #[test]
+    fn should_return_the_fields_that_converted_with_the_errors() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({ name: 'server', retries: 300, timeout: 5 })");
+        let (partial, errors) = Settings::try_from_value_partial(&value, scope);
+        assert_eq!(partial.name.as_deref(), Some("server"));
+        assert_eq!(partial.retries, None);
+        assert_eq!(partial.timeout, Some(Some(5)));
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0].without_preview(), Error::OutOfRange));
+
+        // the regular conversion stops at the same error
+        let err = Settings::try_from_value(&value, scope).expect_err("expected an out of range error");
+        assert!(matches!(err.without_preview(), Error::OutOfRange));
+
+        let value = setup::eval(scope, "({ name: 'server', retries: 3 })");
+        let (partial, errors) = Settings::try_from_value_partial(&value, scope);
+        assert!(errors.is_empty());
+        assert_eq!(partial.retries, Some(3));
+        assert_eq!(partial.timeout, Some(None));
+        let settings = Settings::try_from_value(&value, scope).unwrap();
+        assert_eq!((settings.name.as_str(), settings.retries, settings.timeout), ("server", 3, None));
+    }
+
+    #[test]
+    fn should_return_a_tag_mismatch_with_the_partial_fields() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({ kind: 'user', name: 'server' })");
+        let (partial, errors) = TaggedSettings::try_from_value_partial(&value, scope);
+        assert_eq!(partial.name.as_deref(), Some("server"));
+        assert_eq!(errors.len(), 1);
+        assert!(
+            matches!(&errors[0], Error::TagMismatch { expected, actual } if expected == "settings" && actual == "user")
+        );
+
+        // the regular conversion rejects the same object
+        let err = TaggedSettings::try_from_value(&value, scope).expect_err("expected a tag mismatch");
+        assert!(matches!(err, Error::TagMismatch { .. }));
+
+        let value = setup::eval(scope, "({ kind: 'settings', name: 'server' })");
+        let (partial, errors) = TaggedSettings::try_from_value_partial(&value, scope);
+        assert!(errors.is_empty());
+        assert_eq!(partial.name.as_deref(), Some("server"));
+        let settings = TaggedSettings::try_from_value(&value, scope).unwrap();
+        assert_eq!(settings.name, "server");
+    }
+
+    #[test]
+    fn should_convert_a_char_vec_as_a_string() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let word = Word {
+            letters: "héllo 🦀".chars().collect(),
+            hint: None,
+        };
+        let value = word.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(json.to_rust_string_lossy(scope), r#"{"letters":"héllo 🦀","hint":null}"#);
+
+        let word = Word::try_from_value(&value, scope).unwrap();
+        assert_eq!(word.letters, vec!['h', 'é', 'l', 'l', 'o', ' ', '🦀']);
+        assert_eq!(word.hint, None);
+
+        let value = setup::eval(scope, "({ letters: 'ß', hint: '日本' })");
+        let word = Word::try_from_value(&value, scope).unwrap();
+        assert_eq!(word.letters, vec!['ß']);
+        assert_eq!(word.hint, Some(vec!['日', '本']));
+
+        // an array of characters is not a string
+        let value = setup::eval(scope, "({ letters: ['a', 'b'] })");
+        let err = Word::try_from_value(&value, scope).expect_err("expected a string");
+        assert!(matches!(err.without_preview(), Error::ExpectedString));
+    }
+
+    #[test]
+    fn can_convert_iterables_into_vecs() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let set = setup::eval(scope, "new Set([3, 1, 3, 2])");
+        let vec = v8_derive::helpers::try_as_vec_iterable::<i32>(&set, scope).unwrap();
+        assert_eq!(vec, vec![3, 1, 2]);
+
+        let object = setup::eval(
+            scope,
+            "({ ids: (function* () { yield 1; yield 2; })(), names: new Map([['a', 1], ['b', 2]]).keys() })",
+        );
+        let p = IterableObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.ids, vec![1, 2]);
+        assert_eq!(p.names, Some(vec!["a".to_string(), "b".to_string()]));
+
+        let object = setup::eval(scope, "({ ids: [4, 5] })");
+        let p = IterableObject::try_from_value(&object, scope).expect("failed to deserialize");
+        assert_eq!(p.ids, vec![4, 5]);
+        assert_eq!(p.names, None);
+
+        let object = setup::eval(scope, "({ ids: { length: 1, 0: 1 } })");
+        let err = IterableObject::try_from_value(&object, scope).expect_err("expected a non-iterable");
+        assert!(matches!(err, Error::NotIterable));
+    }
+
+    #[test]
+    fn can_convert_a_boxed_slice() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let slice: Box<[i32]> = Box::new([3, -1, 4]);
+        let value = slice.clone().into_value(scope);
+        assert!(value.is_array());
+        assert_eq!(Box::<[i32]>::try_from_value(&value, scope).unwrap(), slice);
+
+        let value = setup::eval(scope, "[]");
+        assert!(Box::<[i32]>::try_from_value(&value, scope).unwrap().is_empty());
+
+        let value = setup::eval(scope, "'not an array'");
+        let err = Box::<[i32]>::try_from_value(&value, scope).expect_err("expected an array");
+        assert!(matches!(err, Error::ExpectedArray));
+    }
+
+    #[test]
+    fn can_convert_tuples_as_objects_with_positional_keys() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let call = Call {
+            args: (42, "answer".to_string()),
+            extra: Some((true,)),
+        };
+        let value = call.into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let json = setup::eval(scope, "JSON.stringify(value)");
+        assert_eq!(
+            json.to_rust_string_lossy(scope),
+            r#"{"args":{"0":42,"1":"answer"},"extra":{"0":true}}"#
+        );
+        assert!(!setup::eval(scope, "Array.isArray(value.args)").is_true());
+
+        let call = Call::try_from_value(&value, scope).unwrap();
+        assert_eq!(call.args, (42, "answer".to_string()));
+        assert_eq!(call.extra, Some((true,)));
+
+        let value = setup::eval(scope, "({ args: { 1: 'one', 0: 1 }, extra: null })");
+        let call = Call::try_from_value(&value, scope).unwrap();
+        assert_eq!(call.args, (1, "one".to_string()));
+        assert_eq!(call.extra, None);
+    }
+
+    #[test]
+    fn can_convert_a_hashmap_with_a_custom_hasher() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let mut map: HashMap<String, i32, ahash::RandomState> = HashMap::default();
+        map.insert("one".to_string(), 1);
+        map.insert("two".to_string(), 2);
+
+        let value = map.clone().into_value(scope);
+        assert!(value.is_map());
+        let result = HashMap::<String, i32, ahash::RandomState>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, map);
+
+        let value = map.clone().into_object(scope);
+        assert!(!value.is_map());
+        let result = HashMap::<String, i32, ahash::RandomState>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, map);
+    }
+
+    #[test]
+    fn should_preserve_the_order_of_numeric_keys() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let scores = Scores {
+            by_id: vec![(10, "ten".to_string()), (2, "two".to_string()), (7, "seven".to_string())],
+            extra: None,
+        };
+        let value = scores.clone().into_value(scope);
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+
+        let keys = setup::eval(scope, "[...value.by_id.keys()].join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "10,2,7");
+        assert_eq!(Scores::try_from_value(&value, scope).unwrap(), scores);
+
+        // a plain object lists the integer-like keys first, in numeric order
+        let keys = setup::eval(scope, "Object.keys(Object.fromEntries(value.by_id)).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "2,7,10");
+
+        let value = setup::eval(
+            scope,
+            "({ by_id: new Map([[3, 'c'], [1, 'a']]), extra: new Map([['z', 1], ['5', 2]]) })",
+        );
+        let scores = Scores::try_from_value(&value, scope).unwrap();
+        assert_eq!(scores.by_id, vec![(3, "c".to_string()), (1, "a".to_string())]);
+        assert_eq!(scores.extra, Some(vec![("z".to_string(), 1), ("5".to_string(), 2)]));
+
+        let value = setup::eval(scope, "({ by_id: { 3: 'c' } })");
+        let err = Scores::try_from_value(&value, scope).expect_err("expected a map");
+        assert!(matches!(err, Error::ExpectedMap));
+    }
+
+    #[test]
+    fn can_convert_an_owned_local() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let values = vec![setup::eval(scope, "1"), setup::eval(scope, "2"), setup::eval(scope, "3")];
+        let numbers: Vec<u32> = values
+            .into_iter()
+            .map(|value| from_owned(value, scope))
+            .collect::<Result<_, Error>>()
+            .unwrap();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
 }
@@ -0,0 +1,94 @@
+//! Conversions for the `glam` vector and matrix types, represented in JS as arrays of numbers.
+//!
+//! Matrices are flattened in row-major order.
+
+use crate::{errors::Result, helpers::try_as_array, IntoValue, TryFromValue};
+use ::glam::{Mat4, Quat, Vec2, Vec3, Vec4};
+
+macro_rules! impl_glam_vector {
+    ($($t:ty => $n:literal),*) => {
+        $(
+            impl IntoValue for $t {
+                fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+                    self.to_array().to_vec().into_value(scope)
+                }
+            }
+
+            impl TryFromValue for $t {
+                fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+                    try_as_array::<f32, $n>(input, scope).map(|values| <$t>::from_array(values))
+                }
+            }
+        )*
+    };
+}
+
+impl_glam_vector! {
+    Vec2 => 2,
+    Vec3 => 3,
+    Vec4 => 4,
+    Quat => 4
+}
+
+impl IntoValue for Mat4 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        // glam stores the columns, so the rows are the columns of the transposed matrix
+        self.transpose().to_cols_array().to_vec().into_value(scope)
+    }
+}
+
+impl TryFromValue for Mat4 {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let rows = try_as_array::<f32, 16>(input, scope)?;
+        Ok(Mat4::from_cols_array(&rows).transpose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::glam::{Mat4, Vec3};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_vec3() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let vec = Vec3::new(1.0, 2.5, -3.0);
+        let vec_value = vec.into_value(scope);
+        assert_eq!(vec_value.try_cast::<v8::Array>().unwrap().length(), 3);
+        let round_trip = Vec3::try_from_value(&vec_value, scope).expect("Expected a Vec3");
+        assert_eq!(round_trip, vec);
+
+        let too_short = setup::eval(scope, "[1, 2]");
+        let err = Vec3::try_from_value(&too_short, scope).expect_err("Expected a length error");
+        assert!(matches!(err, Error::WrongArrayLength { expected: 3, actual: 2 }));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn can_convert_a_mat4_in_row_major_order() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let translation = Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0));
+        let mat_value = translation.into_value(scope);
+
+        // the translation is the last column, so it ends each of the first three rows
+        let rows = Vec::<f32>::try_from_value(&mat_value, scope).unwrap();
+        assert_eq!(rows.len(), 16);
+        assert_eq!([rows[3], rows[7], rows[11], rows[15]], [1.0, 2.0, 3.0, 1.0]);
+
+        let round_trip = Mat4::try_from_value(&mat_value, scope).expect("Expected a Mat4");
+        assert_eq!(round_trip, translation);
+    }
+}
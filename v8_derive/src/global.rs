@@ -0,0 +1,67 @@
+//! Converting to and from a `v8::Global`, which outlives the handle scope it was created in.
+
+use crate::{errors::Result, IntoValue, TryFromValue};
+
+/// Converts the value into JS and keeps it in a `v8::Global`, e.g. to cache an object that is built once
+/// and handed to many scopes of the same isolate.
+pub fn into_global<T>(value: T, scope: &mut v8::PinScope<'_, '_>) -> v8::Global<v8::Value>
+where
+    T: IntoValue,
+{
+    let value = value.into_value(scope);
+    v8::Global::new(scope, value)
+}
+
+/// Converts the JS value held by the `v8::Global` into `T`.
+///
+/// # Errors
+/// The conversion error when the value cannot be converted
+pub fn from_global<T>(global: &v8::Global<v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<T>
+where
+    T: TryFromValue,
+{
+    let value = v8::Local::new(scope, global);
+    T::try_from_value(&value, scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as v8_derive, from_global, into_global, setup};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    #[derive(Debug, Clone, PartialEq, FromValue, IntoValue)]
+    struct Config {
+        name: String,
+        retries: u32,
+    }
+
+    #[test]
+    fn can_reuse_a_global_across_scopes() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let config = Config {
+            name: "cached".to_string(),
+            retries: 3,
+        };
+
+        let global = {
+            let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+            let scope = &mut scope.init();
+            let context = v8::Context::new(scope, ContextOptions::default());
+            let scope = &mut v8::ContextScope::new(scope, context);
+            into_global(config.clone(), scope)
+        };
+
+        for _ in 0..2 {
+            let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+            let scope = &mut scope.init();
+            let context = v8::Context::new(scope, ContextOptions::default());
+            let scope = &mut v8::ContextScope::new(scope, context);
+
+            let value = v8::Local::new(scope, &global);
+            assert!(value.is_object());
+            assert_eq!(from_global::<Config>(&global, scope).unwrap(), config);
+        }
+    }
+}
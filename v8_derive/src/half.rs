@@ -0,0 +1,137 @@
+//! Conversions for the half `f16`, as a JS number, and for `TypedArray<f16>`, as a `Float32Array`.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_f64,
+    IntoValue, TryFromValue, TypedArray,
+};
+use ::half::f16;
+
+/// Widens into a JS number, which holds every `f16` exactly.
+impl IntoValue for f16 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        f64::from(self).into_value(scope)
+    }
+}
+
+/// Narrows the JS number to the nearest `f16`, so precision is lost, e.g. `0.1` becomes `0.0999755859375`; a
+/// number beyond `f16::MAX` becomes infinity.
+impl TryFromValue for f16 {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        try_as_f64(input, scope).map(f16::from_f64)
+    }
+}
+
+/// Widens into a `Float32Array`, which holds every `f16` exactly; the binding creates a `Float16Array`, but does
+/// not convert it into a `v8::Value`. Like the other typed arrays, this is `undefined` when V8 fails to create
+/// the array.
+impl IntoValue for TypedArray<f16> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let length = self.0.len();
+        let bytes: Vec<u8> = self
+            .0
+            .into_iter()
+            .flat_map(|value| f32::from(value).to_ne_bytes())
+            .collect();
+        let store = v8::ArrayBuffer::new_backing_store_from_vec(bytes).make_shared();
+        let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+
+        match v8::Float32Array::new(scope, buffer, 0, length) {
+            Some(array) => array.into(),
+            None => v8::undefined(scope).into(),
+        }
+    }
+}
+
+/// Reads a `Float16Array`, or a `Float32Array` whose numbers are narrowed to the nearest `f16`.
+impl TryFromValue for TypedArray<f16> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, _scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if let Ok(array) = input.try_cast::<v8::Float32Array>() {
+            let mut bytes = vec![0; array.byte_length()];
+            array.copy_contents(&mut bytes);
+
+            let values = bytes
+                .chunks_exact(size_of::<f32>())
+                .map(|chunk| {
+                    f16::from_f32(f32::from_ne_bytes(
+                        chunk.try_into().expect("chunks have the element size"),
+                    ))
+                })
+                .collect();
+            return Ok(Self(values));
+        }
+
+        let array: v8::Local<v8::Float16Array> = input
+            .try_cast()
+            .map_err(|_| Error::ExpectedTypedArray("Float16Array"))?;
+
+        // copies from the byte offset of the view into its buffer
+        let mut bytes = vec![0; array.byte_length()];
+        array.copy_contents(&mut bytes);
+
+        let values = bytes
+            .chunks_exact(size_of::<f16>())
+            .map(|chunk| f16::from_ne_bytes(chunk.try_into().expect("chunks have the element size")))
+            .collect();
+        Ok(Self(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue, TypedArray};
+    use ::half::f16;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_an_f16() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = f16::from_f32(1.5).into_value(scope);
+        assert_eq!(value.number_value(scope), Some(1.5));
+        assert_eq!(f16::try_from_value(&value, scope).unwrap(), f16::from_f32(1.5));
+
+        // 0.1 has no exact f16 representation
+        let value = setup::eval(scope, "0.1");
+        let narrowed = f16::try_from_value(&value, scope).unwrap();
+        assert_eq!(narrowed, f16::from_f64(0.1));
+        let value = narrowed.into_value(scope);
+        assert_eq!(value.number_value(scope), Some(0.099_975_585_937_5));
+
+        let value = setup::eval(scope, "100000");
+        assert_eq!(f16::try_from_value(&value, scope).unwrap(), f16::INFINITY);
+    }
+
+    #[test]
+    fn can_convert_a_float16_array() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let values = vec![f16::from_f32(-2.0), f16::from_f64(0.1), f16::MAX];
+        let value = TypedArray(values.clone()).into_value(scope);
+        assert!(value.is_float32_array());
+        assert_eq!(
+            TypedArray::<f16>::try_from_value(&value, scope).unwrap(),
+            TypedArray(values)
+        );
+
+        let value = setup::eval(scope, "new Float16Array([1.5, 0.1])");
+        assert_eq!(
+            TypedArray::<f16>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![f16::from_f32(1.5), f16::from_f64(0.1)])
+        );
+
+        let value = setup::eval(scope, "new Int32Array([1, 2])");
+        let err = TypedArray::<f16>::try_from_value(&value, scope).expect_err("expected a Float16Array");
+        assert!(matches!(err, Error::ExpectedTypedArray("Float16Array")));
+    }
+}
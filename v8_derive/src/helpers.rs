@@ -1,5 +1,11 @@
-use crate::{errors, from::TryFromValue};
-use std::{collections::HashMap, hash::BuildHasher};
+use crate::{errors, from::TryFromValue, into::IntoValue};
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
 use v8::GetPropertyNamesArgs;
 
 pub fn get_field_as<T>(
@@ -53,6 +59,168 @@ pub fn get_optional_field_as<T>(
     Ok(Some(inner_value))
 }
 
+/// Reads the property at a dotted path, e.g. `meta.id`, and parses it.
+///
+/// # Errors
+/// `Error::FieldNotFound` with the full path, when any segment of the path is missing
+pub fn get_path_as<T>(
+    path: &str,
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    parse_fn: ParseFn<T>,
+) -> errors::Result<T> {
+    let js_value = get_path_value(path, *input, scope)?.ok_or(errors::Error::FieldNotFound(path.to_string()))?;
+    parse_fn(&js_value, scope)
+}
+
+/// Reads the property at a dotted path, e.g. `meta.id`, and parses it when it is present and not null.
+pub fn get_optional_path_as<T>(
+    path: &str,
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    parse_fn: ParseFn<T>,
+) -> errors::Result<Option<T>> {
+    let Some(js_value) = get_path_value(path, *input, scope)? else {
+        return Ok(None);
+    };
+
+    // check for null
+    if js_value.is_null_or_undefined() {
+        return Ok(None);
+    }
+
+    let inner_value = parse_fn(&js_value, scope)?;
+    Ok(Some(inner_value))
+}
+
+/// Casts the value to a JS array of exactly `length` elements.
+///
+/// # Errors
+/// `Error::ExpectedArray` when the value is not an array, `Error::WrongArrayLength` when it has a different length
+pub fn try_as_array_of_length<'s>(
+    input: &v8::Local<'s, v8::Value>,
+    length: usize,
+) -> errors::Result<v8::Local<'s, v8::Array>> {
+    if !input.is_array() {
+        return Err(errors::Error::ExpectedArray);
+    }
+
+    let array: v8::Local<v8::Array> = input.try_cast()?;
+    let actual = array.length() as usize;
+    if actual != length {
+        return Err(errors::Error::WrongArrayLength {
+            expected: length,
+            actual,
+        });
+    }
+
+    Ok(array)
+}
+
+/// Reads the element at `index` of the array, and parses it.
+pub fn get_element_as<T>(
+    index: u32,
+    array: &v8::Local<'_, v8::Array>,
+    scope: &mut v8::PinScope<'_, '_>,
+    parse_fn: ParseFn<T>,
+) -> errors::Result<T> {
+    let js_value = array
+        .get_index(scope, index)
+        .ok_or(errors::Error::MissingArrayElement { index })?;
+
+    parse_fn(&js_value, scope)
+}
+
+/// Reads the element at `index` of the array, and parses it when it is not null.
+pub fn get_optional_element_as<T>(
+    index: u32,
+    array: &v8::Local<'_, v8::Array>,
+    scope: &mut v8::PinScope<'_, '_>,
+    parse_fn: ParseFn<T>,
+) -> errors::Result<Option<T>> {
+    let Some(js_value) = array.get_index(scope, index) else {
+        return Ok(None);
+    };
+
+    // check for null
+    if js_value.is_null_or_undefined() {
+        return Ok(None);
+    }
+
+    let inner_value = parse_fn(&js_value, scope)?;
+    Ok(Some(inner_value))
+}
+
+/// Walks a dotted path; returns `None` when any of the segments is missing
+fn get_path_value<'s>(
+    path: &str,
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'s, '_>,
+) -> errors::Result<Option<v8::Local<'s, v8::Value>>> {
+    if !input.is_object() {
+        return Err(errors::Error::ExpectedObject);
+    }
+
+    let mut current: Option<v8::Local<'s, v8::Value>> = None;
+    for segment in path.split('.') {
+        let parent = match current {
+            Some(current) => current,
+            None => input,
+        };
+        let Ok(js_object) = parent.try_cast::<v8::Object>() else {
+            return Ok(None);
+        };
+
+        let js_key = v8::String::new(scope, segment)
+            .map(Into::into)
+            .ok_or(errors::Error::InvalidField(path.to_string()))?;
+        if js_object.has(scope, js_key) != Some(true) {
+            return Ok(None);
+        }
+
+        current = js_object.get(scope, js_key);
+        if current.is_none() {
+            return Ok(None);
+        }
+    }
+
+    Ok(current)
+}
+
+/// Sets a property at a dotted path, e.g. `meta.id`, creating the intermediate objects when needed
+pub fn set_path<'s>(
+    path: &str,
+    object: v8::Local<'s, v8::Object>,
+    value: v8::Local<'s, v8::Value>,
+    scope: &mut v8::PinScope<'s, '_>,
+) {
+    let mut target = object;
+    let mut segments = path.split('.').peekable();
+
+    while let Some(segment) = segments.next() {
+        let Some(js_key) = v8::String::new(scope, segment).map(Into::into) else {
+            return;
+        };
+
+        if segments.peek().is_none() {
+            target.set(scope, js_key, value);
+            return;
+        }
+
+        // reuse the intermediate object, when a previous field already created it
+        let existing = target
+            .get(scope, js_key)
+            .and_then(|existing| existing.try_cast::<v8::Object>().ok());
+        target = if let Some(existing) = existing {
+            existing
+        } else {
+            let child = v8::Object::new(scope);
+            target.set(scope, js_key, child.into());
+            child
+        };
+    }
+}
+
 pub type ParseFn<T> = fn(&v8::Local<'_, v8::Value>, &mut v8::PinScope<'_, '_>) -> errors::Result<T>;
 
 pub fn try_as_bool(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<bool> {
@@ -66,32 +234,217 @@ pub fn try_as_string(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<
     Ok(input.to_rust_string_lossy(scope))
 }
 
+/// Converts a JS string into its chars, for a `Vec<char>` field with the `as_string` attribute.
+///
+/// Other JS values, including arrays of single characters, are rejected with `Error::ExpectedString`.
+pub fn try_as_char_vec(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Vec<char>> {
+    if !input.is_string() {
+        return Err(errors::Error::ExpectedString);
+    }
+
+    Ok(input.to_rust_string_lossy(scope).chars().collect())
+}
+
+/// Converts the chars into a single JS string, instead of an array with a string per char.
+pub fn char_vec_into_value<'s>(chars: &[char], scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+    chars.iter().collect::<String>().into_value(scope)
+}
+
 pub fn try_as_i32(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<i32> {
     // use the framework to get the internal convertion
-    input.int32_value(scope).ok_or(errors::Error::ExpectedI32)
+    input
+        .int32_value(scope)
+        .ok_or_else(|| with_preview(errors::Error::ExpectedI32, *input, scope))
 }
 
 pub fn try_as_u32(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u32> {
     if input.is_uint32() {
-        return input.uint32_value(scope).ok_or(errors::Error::ExpectedU32);
+        return input
+            .uint32_value(scope)
+            .ok_or_else(|| with_preview(errors::Error::ExpectedU32, *input, scope));
     }
     if input.is_null_or_undefined() {
         return Ok(0);
     }
     // use the framework to get the internal conversion
-    u32::try_from(input.to_big_int(scope).ok_or(errors::Error::ExpectedU32)?.i64_value().0)
-        .map_err(|_| errors::Error::OutOfRange)
+    let Some(i) = input.to_big_int(scope) else {
+        return Err(with_preview(errors::Error::ExpectedU32, *input, scope));
+    };
+    u32::try_from(i.i64_value().0).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope))
+}
+
+/// Like [`try_as_i32`], but also accepts a `BigInt`, e.g. `5n`, and the wrapper objects of numbers and
+/// `BigInt`s, e.g. `new Number(5)`.
+///
+/// # Errors
+/// `Error::OutOfRange` when the `BigInt` does not fit in an `i32`
+pub fn try_as_i32_coerce(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<i32> {
+    let unwrapped = unwrap_number_object(*input, scope);
+    let input = unwrapped.as_ref().unwrap_or(input);
+
+    match try_as_big_int_i64(*input, scope) {
+        Some(value) => i32::try_from(value?).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope)),
+        None => try_as_i32(input, scope),
+    }
+}
+
+/// Like [`try_as_u32`], but also accepts a `BigInt`, e.g. `5n`, and the wrapper objects of numbers and
+/// `BigInt`s, e.g. `new Number(5)`.
+///
+/// # Errors
+/// `Error::OutOfRange` when the `BigInt` does not fit in a `u32`
+pub fn try_as_u32_coerce(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u32> {
+    let unwrapped = unwrap_number_object(*input, scope);
+    let input = unwrapped.as_ref().unwrap_or(input);
+
+    match try_as_big_int_i64(*input, scope) {
+        Some(value) => u32::try_from(value?).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope)),
+        None => try_as_u32(input, scope),
+    }
+}
+
+/// The primitive inside a `Number` or `BigInt` wrapper object, e.g. `new Number(5)`; `None` for other values
+fn unwrap_number_object<'s>(
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'s, '_>,
+) -> Option<v8::Local<'s, v8::Value>> {
+    if input.is_number_object() {
+        input.to_number(scope).map(Into::into)
+    } else if input.is_big_int_object() {
+        input.to_big_int(scope).map(Into::into)
+    } else {
+        None
+    }
+}
+
+/// Reads a `BigInt` as an `i64`; returns `None` when the value is not a `BigInt`
+fn try_as_big_int_i64(
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> Option<errors::Result<i64>> {
+    let big_int: v8::Local<v8::BigInt> = input.try_cast().ok()?;
+    let (value, lossless) = big_int.i64_value();
+    if !lossless {
+        return Some(Err(with_preview(errors::Error::OutOfRange, input, scope)));
+    }
+
+    Some(Ok(value))
 }
 
 pub fn try_as_i64(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<i64> {
     // use the framework to get the internal convertion
-    let i = input.to_big_int(scope).ok_or(errors::Error::ExpectedI64)?;
+    let Some(i) = input.to_big_int(scope) else {
+        return Err(with_preview(errors::Error::ExpectedI64, *input, scope));
+    };
     Ok(i.i64_value().0)
 }
 
+/// Converts a `BigInt`, or any value that converts into one, into a `u64`.
+///
+/// # Errors
+/// `Error::OutOfRange` when the `BigInt` is negative or does not fit in a `u64`
+pub fn try_as_u64(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u64> {
+    // use the framework to get the internal convertion
+    let Some(i) = input.to_big_int(scope) else {
+        return Err(with_preview(errors::Error::ExpectedU64, *input, scope));
+    };
+    match i.u64_value() {
+        (value, true) => Ok(value),
+        (_, false) => Err(with_preview(errors::Error::OutOfRange, *input, scope)),
+    }
+}
+
+/// The largest integer up to which every integer is exactly representable as a JS number, `Number.MAX_SAFE_INTEGER`
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// `Number.MAX_SAFE_INTEGER` as an integer
+pub(crate) const MAX_SAFE_INTEGER_U64: u64 = 9_007_199_254_740_991;
+
+/// Like [`try_as_i64`], but also accepts a JS number, as long as it holds an integer exactly. A `BigInt` is
+/// always converted losslessly.
+///
+/// # Errors
+/// `Error::LossyConversion` when the number has a fraction, or lies beyond `Number.MAX_SAFE_INTEGER`, where a
+/// double no longer represents every integer; `Error::OutOfRange` when the `BigInt` does not fit in an `i64`
+pub fn try_as_i64_strict(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<i64> {
+    if let Some(value) = try_as_big_int_i64(*input, scope) {
+        return value;
+    }
+
+    if !input.is_number() {
+        return Err(with_preview(errors::Error::ExpectedI64, *input, scope));
+    }
+
+    let number = try_as_safe_integer(*input, scope)?;
+    // a safe integer fits in an `i64`
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(number as i64)
+}
+
+/// Like [`try_as_u64`], but also accepts a JS number, as long as it holds an integer exactly. A `BigInt` is
+/// always converted losslessly.
+///
+/// # Errors
+/// `Error::LossyConversion` when the number has a fraction, or lies beyond `Number.MAX_SAFE_INTEGER`, where a
+/// double no longer represents every integer; `Error::OutOfRange` when the value is negative, or when the
+/// `BigInt` does not fit in a `u64`
+pub fn try_as_u64_strict(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u64> {
+    if input.is_big_int() {
+        return try_as_u64(input, scope);
+    }
+
+    if !input.is_number() {
+        return Err(with_preview(errors::Error::ExpectedU64, *input, scope));
+    }
+
+    let number = try_as_safe_integer(*input, scope)?;
+    if number < 0.0 {
+        return Err(with_preview(errors::Error::OutOfRange, *input, scope));
+    }
+
+    // a non-negative safe integer fits in a `u64`
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok(number as u64)
+}
+
+/// Reads a JS number that holds an integer exactly
+fn try_as_safe_integer(input: v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<f64> {
+    match input.number_value(scope) {
+        Some(number) if number.fract() == 0.0 && number.abs() <= MAX_SAFE_INTEGER => Ok(number),
+        _ => Err(with_preview(errors::Error::LossyConversion, input, scope)),
+    }
+}
+
+/// Converts the `i64` into a JS number when it lies within `±Number.MAX_SAFE_INTEGER`, where a double holds it
+/// exactly, and into a `BigInt` beyond that.
+#[allow(clippy::cast_precision_loss)]
+pub fn i64_into_value_if_safe<'s>(value: i64, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+    if value.unsigned_abs() <= MAX_SAFE_INTEGER_U64 {
+        v8::Number::new(scope, value as f64).into()
+    } else {
+        v8::BigInt::new_from_i64(scope, value).into()
+    }
+}
+
+/// Converts the `u64` into a JS number up to `Number.MAX_SAFE_INTEGER`, where a double holds it exactly, and into
+/// a `BigInt` beyond that.
+#[allow(clippy::cast_precision_loss)]
+pub fn u64_into_value_if_safe<'s>(value: u64, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+    if value <= MAX_SAFE_INTEGER_U64 {
+        v8::Number::new(scope, value as f64).into()
+    } else {
+        v8::BigInt::new_from_u64(scope, value).into()
+    }
+}
+
 pub fn try_as_f64(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<f64> {
     // use the framework to get the internal convertion
-    input.number_value(scope).ok_or(errors::Error::ExpectedF64)
+    input
+        .number_value(scope)
+        .ok_or_else(|| with_preview(errors::Error::ExpectedF64, *input, scope))
 }
 
 #[allow(clippy::cast_possible_truncation)]
@@ -102,9 +455,135 @@ pub fn try_as_f32(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_,
 
 pub fn try_as_i8(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<i8> {
     let i = try_as_i32(input, scope)?;
-    i8::try_from(i).map_err(|_| errors::Error::OutOfRange)
+    i8::try_from(i).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope))
+}
+
+pub fn try_as_u8(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u8> {
+    let i = try_as_u32(input, scope)?;
+    u8::try_from(i).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope))
 }
 
+pub fn try_as_u16(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<u16> {
+    let i = try_as_u32(input, scope)?;
+    u16::try_from(i).map_err(|_| with_preview(errors::Error::OutOfRange, *input, scope))
+}
+
+/// Maximum number of characters of a JS value quoted in an error
+const PREVIEW_LENGTH: usize = 64;
+
+/// Attaches a short preview of the offending JS value to the error.
+///
+/// Only primitives are rendered; objects are described by their type, so no (side-effecting) `toString`
+/// is called.
+fn with_preview(
+    error: errors::Error,
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Error {
+    let preview = if input.is_string() {
+        format!("\"{}\"", truncate_preview(input.to_rust_string_lossy(scope)))
+    } else if input.is_big_int() {
+        format!("{}n", truncate_preview(input.to_rust_string_lossy(scope)))
+    } else if input.is_number() || input.is_boolean() || input.is_null_or_undefined() {
+        input.to_rust_string_lossy(scope)
+    } else if input.is_object() && input.type_repr() == "unknown" {
+        // a plain object
+        "object".to_string()
+    } else {
+        input.type_repr().to_string()
+    };
+
+    errors::Error::WithPreview {
+        preview,
+        source: Box::new(error),
+    }
+}
+
+fn truncate_preview(value: String) -> String {
+    match value.char_indices().nth(PREVIEW_LENGTH) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value,
+    }
+}
+
+/// Reads a number of milliseconds as a `Duration`.
+///
+/// The fractional part is kept with nanosecond precision, rounded to the nearest nanosecond. Very large
+/// values lose sub-millisecond precision because of the `f64` representation of JS numbers.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn try_as_duration(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Duration> {
+    let millis = try_as_f64(input, scope)?;
+    if !millis.is_finite() || millis < 0.0 || millis >= u64::MAX as f64 {
+        return Err(errors::Error::OutOfRange);
+    }
+
+    let whole_millis = millis.trunc();
+    let nanos = ((millis - whole_millis) * 1_000_000.0).round();
+    Ok(Duration::from_millis(whole_millis as u64) + Duration::from_nanos(nanos as u64))
+}
+
+fn try_as_parsed<T>(
+    input: v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+    error: fn(String) -> errors::Error,
+) -> errors::Result<T>
+where
+    T: FromStr,
+{
+    let value = try_as_string(&input, scope)?;
+    value.parse().map_err(|_| error(value))
+}
+
+pub fn try_as_ip_addr(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<IpAddr> {
+    try_as_parsed(*input, scope, errors::Error::InvalidAddress)
+}
+
+pub fn try_as_ipv4_addr(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Ipv4Addr> {
+    try_as_parsed(*input, scope, errors::Error::InvalidAddress)
+}
+
+pub fn try_as_ipv6_addr(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Ipv6Addr> {
+    try_as_parsed(*input, scope, errors::Error::InvalidAddress)
+}
+
+pub fn try_as_socket_addr(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<SocketAddr> {
+    try_as_parsed(*input, scope, errors::Error::InvalidAddress)
+}
+
+/// Converts a JS string holding a number, e.g. `"18446744073709551615"`, by parsing it.
+///
+/// Other JS values, including numbers, are rejected with `Error::ExpectedString`.
+pub fn try_as_numeric_string<T>(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<T>
+where
+    T: FromStr,
+{
+    if !input.is_string() {
+        return Err(errors::Error::ExpectedString);
+    }
+
+    try_as_parsed(*input, scope, errors::Error::InvalidNumber)
+}
+
+/// Converts a JS array into a `Vec`.
+///
+/// The holes of a sparse array (`[1, , 3]`) read as `undefined`, and are converted like any other
+/// `undefined` value; use a `Vec<Option<T>>` to keep track of them, or [`try_as_vec_skip_holes`] to leave
+/// them out.
+///
+/// Nested arrays, of any length, convert into a `Vec<Vec<T>>`; every level of nesting is one recursive call.
 pub fn try_as_vec<T>(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Vec<T>>
 where
     T: TryFromValue,
@@ -119,10 +598,117 @@ where
     let mut result = Vec::with_capacity(length as usize);
 
     for i in 0..length {
-        let Some(element) = array.get_index(scope, i) else {
-            // this should never happen
+        // fails when reading the element throws, e.g. in a getter
+        let element = array
+            .get_index(scope, i)
+            .ok_or(errors::Error::MissingArrayElement { index: i })?;
+
+        let element = T::try_from_value(&element, scope)?;
+        result.push(element);
+    }
+
+    Ok(result)
+}
+
+/// Converts a JS array of exactly `N` elements into an array.
+///
+/// # Errors
+/// `Error::WrongArrayLength` when the JS array has a different length
+pub fn try_as_array<T, const N: usize>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<[T; N]>
+where
+    T: TryFromValue,
+{
+    let values: Vec<T> = try_as_vec(input, scope)?;
+    let actual = values.len();
+    values
+        .try_into()
+        .map_err(|_| errors::Error::WrongArrayLength { expected: N, actual })
+}
+
+/// Converts any JS iterable, e.g. a `Set`, the entries of a `Map` or a generator, into a `Vec` by draining its
+/// `Symbol.iterator`; an array is iterable as well.
+///
+/// A value without a `Symbol.iterator` method is rejected with `Error::NotIterable`. When a call of the `next`
+/// method throws, `Error::MissingArrayElement` is returned with the position of the element.
+pub fn try_as_vec_iterable<T>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Vec<T>>
+where
+    T: TryFromValue,
+{
+    let object = input.to_object(scope).ok_or(errors::Error::NotIterable)?;
+    let iterator_key = v8::Symbol::get_iterator(scope).into();
+    let iterator_fn = object
+        .get(scope, iterator_key)
+        .and_then(|iterator_fn| v8::Local::<v8::Function>::try_from(iterator_fn).ok())
+        .ok_or(errors::Error::NotIterable)?;
+    let iterator = iterator_fn
+        .call(scope, *input, &[])
+        .and_then(|iterator| v8::Local::<v8::Object>::try_from(iterator).ok())
+        .ok_or(errors::Error::NotIterable)?;
+
+    let next_key = v8::String::new(scope, "next").ok_or(errors::Error::NotIterable)?.into();
+    let next = iterator
+        .get(scope, next_key)
+        .and_then(|next| v8::Local::<v8::Function>::try_from(next).ok())
+        .ok_or(errors::Error::NotIterable)?;
+
+    let done_key = v8::String::new(scope, "done").ok_or(errors::Error::NotIterable)?.into();
+    let value_key = v8::String::new(scope, "value")
+        .ok_or(errors::Error::NotIterable)?
+        .into();
+    let mut result = Vec::new();
+
+    for index in 0.. {
+        let step = next
+            .call(scope, iterator.into(), &[])
+            .and_then(|step| v8::Local::<v8::Object>::try_from(step).ok())
+            .ok_or(errors::Error::MissingArrayElement { index })?;
+
+        if step.get(scope, done_key).is_some_and(|done| done.boolean_value(scope)) {
+            break;
+        }
+
+        let element = step
+            .get(scope, value_key)
+            .ok_or(errors::Error::MissingArrayElement { index })?;
+        result.push(T::try_from_value(&element, scope)?);
+    }
+
+    Ok(result)
+}
+
+/// Converts a JS array into a `Vec`, leaving out the holes of a sparse array (`[1, , 3]`).
+///
+/// Elements that are explicitly set to `undefined` are not holes, and are still converted.
+pub fn try_as_vec_skip_holes<T>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Vec<T>>
+where
+    T: TryFromValue,
+{
+    if !input.is_array() {
+        return Err(errors::Error::ExpectedArray);
+    }
+
+    let array: v8::Local<v8::Array> = input.try_cast()?;
+    let length = array.length();
+
+    let mut result = Vec::with_capacity(length as usize);
+
+    for i in 0..length {
+        if array.has_index(scope, i) != Some(true) {
             continue;
-        };
+        }
+
+        let element = array
+            .get_index(scope, i)
+            .ok_or(errors::Error::MissingArrayElement { index: i })?;
 
         let element = T::try_from_value(&element, scope)?;
         result.push(element);
@@ -131,6 +717,65 @@ where
     Ok(result)
 }
 
+/// Converts a JS `Map` into its `(key, value)` entries, in the insertion order of the map; used for the fields with
+/// the `preserve_key_order` attribute.
+///
+/// Other JS values, including plain objects, are rejected with `Error::ExpectedMap`, as an object lists its
+/// integer-like keys first.
+pub fn try_as_ordered_pairs<K, T>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<Vec<(K, T)>>
+where
+    K: TryFromValue,
+    T: TryFromValue,
+{
+    if !input.is_map() {
+        return Err(errors::Error::ExpectedMap);
+    }
+
+    let js_map: v8::Local<v8::Map> = input.try_cast()?;
+    let js_array = js_map.as_array(scope);
+    let mut result = Vec::with_capacity(js_map.size());
+    for i in (0..js_array.length()).step_by(2) {
+        let (Some(key), Some(value)) = (js_array.get_index(scope, i), js_array.get_index(scope, i + 1)) else {
+            continue;
+        };
+
+        result.push((K::try_from_value(&key, scope)?, T::try_from_value(&value, scope)?));
+    }
+
+    Ok(result)
+}
+
+/// Converts the `(key, value)` entries into a JS `Map`, which keeps the order of the entries, also for numeric keys
+/// that a plain object would list first.
+pub fn ordered_pairs_into_value<'s, K, T>(
+    pairs: Vec<(K, T)>,
+    scope: &mut v8::PinScope<'s, '_>,
+) -> v8::Local<'s, v8::Value>
+where
+    K: IntoValue,
+    T: IntoValue,
+{
+    let map = v8::Map::new(scope);
+    for (key, value) in pairs {
+        let js_key = key.into_value(scope);
+        let js_val = value.into_value(scope);
+        map.set(scope, js_key, js_val);
+    }
+
+    map.into()
+}
+
+/// Converts a JS `Map`, or the own properties of a JS object, into a `HashMap` with `String` keys; see
+/// [`try_as_hashmap_keyed`] for other key types.
+///
+/// The keys of a `Map` and of an object are converted like `String(key)`, so the `Map` key `1` and the object
+/// key `1` both become `"1"`.
+///
+/// # Errors
+/// `Error::ExpectedMap` when the value is not an object, or the conversion error of a value
 pub fn try_as_hashmap<T, S>(
     input: &v8::Local<'_, v8::Value>,
     scope: &mut v8::PinScope<'_, '_>,
@@ -138,12 +783,36 @@ pub fn try_as_hashmap<T, S>(
 where
     T: TryFromValue,
     S: BuildHasher + Default,
+{
+    try_as_hashmap_keyed(input, scope)
+}
+
+/// Converts a JS `Map`, or the own properties of a JS object, into a `HashMap`.
+///
+/// The keys of an object are strings, so they only convert into key types that can be read from a JS string,
+/// e.g. `String`, or a unit-only enum deriving `FromValue`.
+///
+/// A `Map` is also an object, so it is checked for first; its entries are read instead of its properties. For
+/// a `String` key, the keys of both are converted like `String(key)`, so the `Map` key `1` and the object key
+/// `1` both become `"1"`.
+///
+/// A `String` value is converted like `String(value)` as well, so a `HashMap<String, String>` reads any object:
+/// a number becomes `"30"`, `null` becomes `"null"`, an array `"1,2"` and a nested object `"[object Object]"`,
+/// not its JSON.
+pub fn try_as_hashmap_keyed<K, T, S>(
+    input: &v8::Local<'_, v8::Value>,
+    scope: &mut v8::PinScope<'_, '_>,
+) -> errors::Result<HashMap<K, T, S>>
+where
+    K: TryFromValue + Eq + Hash,
+    T: TryFromValue,
+    S: BuildHasher + Default,
 {
     if !(input.is_map() || input.is_object()) {
         return Err(errors::Error::ExpectedMap);
     }
 
-    let mut result: HashMap<String, T, S> = HashMap::with_hasher(S::default());
+    let mut result: HashMap<K, T, S> = HashMap::with_hasher(S::default());
 
     if input.is_map() {
         let js_map: v8::Local<v8::Map> = input.try_cast()?;
@@ -153,7 +822,7 @@ where
                 continue;
             };
 
-            let key = key.to_rust_string_lossy(scope);
+            let key = K::try_from_value(&key, scope)?;
             let value = T::try_from_value(&value, scope)?;
             result.insert(key, value);
         }
@@ -175,7 +844,7 @@ where
             .get(scope, key)
             .ok_or(errors::Error::FailedToGetPropertyNames)?;
         let value = T::try_from_value(&value, scope)?;
-        let key = key.to_rust_string_lossy(scope);
+        let key = K::try_from_value(&key, scope)?;
         result.insert(key, value);
     }
 
@@ -184,7 +853,7 @@ where
 
 #[cfg(test)]
 pub(crate) mod setup {
-    use super::{try_as_bool, try_as_i8};
+    use super::{try_as_bool, try_as_hashmap, try_as_hashmap_keyed, try_as_i8};
     use crate::{try_as_i32, try_as_u32};
     use std::sync::Once;
     use v8::Value;
@@ -194,6 +863,13 @@ pub(crate) mod setup {
         initialize_once();
     }
 
+    /// Compile and run a JS snippet, returning its completion value
+    pub(crate) fn eval<'s>(scope: &mut v8::PinScope<'s, '_>, source: &str) -> v8::Local<'s, Value> {
+        let source = v8::String::new(scope, source).unwrap();
+        let script = v8::Script::compile(scope, source, None).expect("failed to compile");
+        script.run(scope).expect("failed to run")
+    }
+
     fn initialize_once() {
         static START: Once = Once::new();
         START.call_once(|| {
@@ -532,4 +1208,35 @@ pub(crate) mod setup {
         // - expect to be able to convert and result in false
         result.expect_err("Expected to NOT be able to convert");
     }
+
+    #[test]
+    fn test_try_hashmap_with_string_or_typed_keys() {
+        // given
+        // - v8 is all ok
+        setup_test();
+        let isolate = &mut v8::Isolate::new(v8::CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, v8::ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // given
+        // - a map with number keys
+        let value = eval(scope, "new Map([[1, 10], [2, 20]])");
+        // when
+        // - try to convert with string keys
+        let result: std::collections::HashMap<String, i32> = try_as_hashmap::<i32, _>(&value, scope).unwrap();
+        // then
+        // - expect the keys to be converted like String(key)
+        assert_eq!(result.get("1"), Some(&10));
+        assert_eq!(result.get("2"), Some(&20));
+
+        // when
+        // - try to convert with typed keys
+        let result: std::collections::HashMap<u32, i32> = try_as_hashmap_keyed(&value, scope).unwrap();
+        // then
+        // - expect the keys to be converted like the values
+        assert_eq!(result.get(&1), Some(&10));
+        assert_eq!(result.get(&2), Some(&20));
+    }
 }
@@ -0,0 +1,74 @@
+//! Interning the variant names of derived enums, so converting a variant does not allocate a new JS string.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// The interned strings of an isolate, by their content
+#[derive(Default)]
+struct InternedStrings(RefCell<HashMap<&'static str, v8::Global<v8::String>>>);
+
+/// The JS string with the content of `name`, created once per isolate and reused by later calls; the `IntoValue`
+/// derive uses it for the variant names of enums.
+///
+/// The string is internalized, so it is also the same string as a JS literal with that content.
+pub fn interned_string<'s>(scope: &mut v8::PinScope<'s, '_>, name: &'static str) -> v8::Local<'s, v8::Value> {
+    let strings = scope.get_slot::<Rc<InternedStrings>>().cloned().unwrap_or_else(|| {
+        let strings = Rc::new(InternedStrings::default());
+        scope.set_slot(strings.clone());
+        strings
+    });
+
+    if let Some(string) = strings.0.borrow().get(name) {
+        return v8::Local::new(scope, string).into();
+    }
+
+    let Some(string) = v8::String::new_from_utf8(scope, name.as_bytes(), v8::NewStringType::Internalized) else {
+        return v8::String::empty(scope).into();
+    };
+    strings.0.borrow_mut().insert(name, v8::Global::new(scope, string));
+    string.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::interned_string;
+    use crate::{self as v8_derive, setup, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::{FromValue, IntoValue};
+
+    #[derive(Debug, Clone, Copy, PartialEq, FromValue, IntoValue)]
+    enum Level {
+        Debug,
+        Warning,
+    }
+
+    #[test]
+    fn should_reuse_the_interned_variant_names() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let first = Level::Warning.into_value(scope);
+        for _ in 0..100_000 {
+            let scope = std::pin::pin!(v8::HandleScope::new(scope));
+            let scope = &mut scope.init();
+            let value = Level::Warning.into_value(scope);
+            assert!(value.strict_equals(first));
+        }
+
+        // the same JS string, not just an equal one
+        let value = Level::Warning.into_value(scope);
+        assert!(value == first);
+        assert_eq!(value.to_rust_string_lossy(scope), "Warning");
+        assert_eq!(Level::try_from_value(&value, scope).unwrap(), Level::Warning);
+
+        let value = Level::Debug.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "Debug");
+        assert_eq!(Level::try_from_value(&value, scope).unwrap(), Level::Debug);
+
+        let value = interned_string(scope, "Warning");
+        assert!(value == first);
+    }
+}
@@ -1,8 +1,17 @@
 //! This module provides a trait to convert a Rust type into a v8 Value.
 
 #[cfg(feature = "json")]
-use crate::json::json_to_v8;
-use std::{collections::HashMap, hash::BuildHasher};
+use crate::json::{json_map_to_v8, json_to_v8};
+use std::{
+    borrow::Cow,
+    collections::{BinaryHeap, HashMap},
+    hash::BuildHasher,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    num::Wrapping,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+    time::Duration,
+};
 
 /// The `IntoValue` trait is used to convert a Rust type into a v8 Value.
 pub trait IntoValue {
@@ -10,10 +19,39 @@ pub trait IntoValue {
 }
 
 /// The `IntoObject` trait is used to convert a Rust type into a v8 Value.
+///
+/// The keys of a plain JS object are not kept in insertion order: the integer-like keys, e.g. the keys of a
+/// `HashMap<u32, T>`, are listed first, in numeric order. Convert into a JS `Map` with `IntoValue` to keep the order,
+/// e.g. of a `Vec<(K, V)>` field with the `preserve_key_order` attribute.
 pub trait IntoObject {
     fn into_object<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>;
 }
 
+/// The `ExtendObject` trait sets the fields of a struct on an existing v8 Object, instead of creating a new one.
+///
+/// It is implemented by the `IntoValue` derive for structs that convert into an object, so several structs can be
+/// merged into one JS object; a property that is already set is overwritten.
+pub trait ExtendObject {
+    fn write_into<'s>(self, object: v8::Local<'s, v8::Object>, scope: &mut v8::PinScope<'s, '_>);
+}
+
+/// The `IntoSorted` trait converts a map with its entries inserted in key order, instead of the iteration order of
+/// the map, so the output is deterministic, e.g. for golden-file tests.
+///
+/// Note that JS lists the integer-like keys of a plain object first, in numeric order, whatever the insertion order.
+pub trait IntoSorted {
+    /// Converts into a JS `Map`, like `IntoValue`
+    fn into_value_sorted<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>;
+    /// Converts into a plain JS object, like `IntoObject`
+    fn into_object_sorted<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>;
+}
+
+/// The `IntoObjectSkipNone` trait converts a map of optional values into a v8 Object, leaving out the keys
+/// whose value is `None` instead of setting them to `null`.
+pub trait IntoObjectSkipNone {
+    fn into_object_skip_none<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>;
+}
+
 impl IntoValue for bool {
     fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
         v8::Boolean::new(scope, self).into()
@@ -26,6 +64,18 @@ impl IntoValue for i32 {
     }
 }
 
+impl IntoValue for u8 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        u32::from(self).into_value(scope)
+    }
+}
+
+impl IntoValue for u16 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        u32::from(self).into_value(scope)
+    }
+}
+
 impl IntoValue for u32 {
     fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
         v8::Integer::new_from_unsigned(scope, self).into()
@@ -38,6 +88,12 @@ impl IntoValue for i64 {
     }
 }
 
+impl IntoValue for u64 {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        v8::BigInt::new_from_u64(scope, self).into()
+    }
+}
+
 impl IntoValue for f64 {
     fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
         v8::Number::new(scope, self).into()
@@ -56,6 +112,114 @@ impl IntoValue for String {
     }
 }
 
+/// A `Duration` is represented in JS as a number of milliseconds, including the sub-millisecond fraction
+impl IntoValue for Duration {
+    #[allow(clippy::cast_precision_loss)]
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let millis = self.as_secs() as f64 * 1000.0 + f64::from(self.subsec_nanos()) / 1_000_000.0;
+        millis.into_value(scope)
+    }
+}
+
+/// Implements `IntoValue` for types that are represented in JS by their `Display` string
+macro_rules! impl_into_value_as_string {
+    ($($t:ty),*) => {
+        $(
+            impl IntoValue for $t {
+                fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+                    self.to_string().into_value(scope)
+                }
+            }
+        )*
+    };
+}
+
+impl_into_value_as_string! {
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr
+}
+
+/// Builds the JS string straight from the borrowed or owned value, without an intermediate `String`.
+///
+/// Together with the generic `HashMap` implementations this also covers `Cow`-keyed maps on both
+/// `IntoValue` and `IntoObject`.
+impl IntoValue for Cow<'_, str> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        v8::String::new(scope, &self).unwrap_or(v8::String::empty(scope)).into()
+    }
+}
+
+impl IntoValue for Box<str> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        v8::String::new(scope, &self).unwrap_or(v8::String::empty(scope)).into()
+    }
+}
+
+impl IntoValue for Arc<str> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        v8::String::new(scope, &self).unwrap_or(v8::String::empty(scope)).into()
+    }
+}
+
+impl<T> IntoValue for Box<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        (*self).into_value(scope)
+    }
+}
+
+impl<T> IntoValue for Wrapping<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.0.into_value(scope)
+    }
+}
+
+/// A `Range` is represented in JS as a `{ start, end }` object
+impl<T> IntoValue for Range<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let object = v8::Object::new(scope);
+        set_property(object, "start", self.start, scope);
+        set_property(object, "end", self.end, scope);
+        object.into()
+    }
+}
+
+/// A `RangeInclusive` is represented in JS as a `{ start, end, inclusive: true }` object
+impl<T> IntoValue for RangeInclusive<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let object = v8::Object::new(scope);
+        let (start, end) = self.into_inner();
+        set_property(object, "start", start, scope);
+        set_property(object, "end", end, scope);
+        set_property(object, "inclusive", true, scope);
+        object.into()
+    }
+}
+
+fn set_property<'s>(
+    object: v8::Local<'s, v8::Object>,
+    key: &str,
+    value: impl IntoValue,
+    scope: &mut v8::PinScope<'s, '_>,
+) {
+    let js_key = v8::String::new(scope, key).unwrap().into();
+    let js_val = value.into_value(scope);
+    object.set(scope, js_key, js_val);
+}
+
 impl<T> IntoValue for Option<T>
 where
     T: IntoValue,
@@ -68,6 +232,43 @@ where
     }
 }
 
+/// Converts a value by reference, so it is not moved out of its owner, e.g. an optional field of a struct, or the
+/// elements of a `Vec<&T>` filtered from an owned collection; the value is cloned first.
+impl<T> IntoValue for &T
+where
+    T: IntoValue + Clone,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.clone().into_value(scope)
+    }
+}
+
+/// A tuple becomes an array of its elements, e.g. `[1, "one"]` for a `(i32, String)`
+macro_rules! impl_into_value_for_tuple {
+    ($length:literal => $($index:tt: $t:ident),+) => {
+        impl<$($t),+> IntoValue for ($($t,)+)
+        where
+            $($t: IntoValue),+
+        {
+            fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+                let array = v8::Array::new(scope, $length);
+                $(
+                    let element = self.$index.into_value(scope);
+                    array.set_index(scope, $index, element);
+                )+
+                array.into()
+            }
+        }
+    };
+}
+
+impl_into_value_for_tuple!(1 => 0: A);
+impl_into_value_for_tuple!(2 => 0: A, 1: B);
+impl_into_value_for_tuple!(3 => 0: A, 1: B, 2: C);
+impl_into_value_for_tuple!(4 => 0: A, 1: B, 2: C, 3: D);
+impl_into_value_for_tuple!(5 => 0: A, 1: B, 2: C, 3: D, 4: E);
+impl_into_value_for_tuple!(6 => 0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+
 impl<T> IntoValue for Vec<T>
 where
     T: IntoValue,
@@ -86,6 +287,26 @@ where
     }
 }
 
+impl<T> IntoValue for Box<[T]>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.into_vec().into_value(scope)
+    }
+}
+
+/// Converts into a JS array in the internal order of the heap, which is not sorted; only the largest element is
+/// guaranteed to come first.
+impl<T> IntoValue for BinaryHeap<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.into_vec().into_value(scope)
+    }
+}
+
 impl<K, T, S> IntoValue for HashMap<K, T, S>
 where
     K: IntoValue,
@@ -142,6 +363,105 @@ where
     }
 }
 
+impl<K, T, S> IntoSorted for HashMap<K, T, S>
+where
+    K: IntoValue + Ord,
+    T: IntoValue,
+    S: BuildHasher,
+{
+    fn into_value_sorted<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let object = v8::Map::new(scope);
+
+        for (key, value) in sorted_entries(self) {
+            let js_key = key.into_value(scope);
+            let js_val = value.into_value(scope);
+            object.set(scope, js_key, js_val);
+        }
+
+        object.into()
+    }
+
+    fn into_object_sorted<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let object = v8::Object::new(scope);
+
+        for (key, value) in sorted_entries(self) {
+            let js_key = key.into_value(scope);
+            let js_val = value.into_value(scope);
+            object.set(scope, js_key, js_val);
+        }
+
+        object.into()
+    }
+}
+
+fn sorted_entries<K, T, S>(map: HashMap<K, T, S>) -> Vec<(K, T)>
+where
+    K: Ord,
+{
+    let mut entries: Vec<(K, T)> = map.into_iter().collect();
+    entries.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+    entries
+}
+
+impl<K, T, S> IntoObjectSkipNone for HashMap<K, Option<T>, S>
+where
+    K: IntoValue,
+    T: IntoValue,
+    S: BuildHasher,
+{
+    fn into_object_skip_none<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let object = v8::Object::new(scope);
+
+        for (key, value) in self {
+            let Some(value) = value else {
+                continue;
+            };
+
+            let js_key = key.into_value(scope);
+            let js_val = value.into_value(scope);
+            object.set(scope, js_key, js_val);
+        }
+
+        object.into()
+    }
+}
+
+/// Convert the items into a JS Array, with each element converted by `IntoObject` instead of `IntoValue`;
+/// e.g. a `Vec<HashMap<String, i32>>` becomes an array of objects instead of an array of `Map`s.
+pub fn into_object_array<'s, T>(items: Vec<T>, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>
+where
+    T: IntoObject,
+{
+    let l = i32::try_from(items.len()).unwrap_or(i32::MAX);
+    let array = v8::Array::new(scope, l);
+
+    for (i, item) in items.into_iter().enumerate() {
+        let el = item.into_object(scope);
+        let idx = u32::try_from(i).unwrap_or(u32::MAX);
+        array.set_index(scope, idx, el);
+    }
+
+    array.into()
+}
+
+/// Convert the key-value pairs into a plain JS object, without collecting them into a map first; e.g. for an
+/// object whose keys are only known at runtime. A later pair with the same key overwrites the earlier one.
+pub fn object_from_pairs<'s, I, V>(pairs: I, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value>
+where
+    I: IntoIterator<Item = (String, V)>,
+    V: IntoValue,
+{
+    let object = v8::Object::new(scope);
+
+    for (key, value) in pairs {
+        let js_key = key.into_value(scope);
+        let js_val = value.into_value(scope);
+        object.set(scope, js_key, js_val);
+    }
+
+    object.into()
+}
+
 #[cfg(feature = "json")]
 impl IntoValue for serde_json::Value {
     fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
@@ -149,14 +469,79 @@ impl IntoValue for serde_json::Value {
     }
 }
 
+#[cfg(feature = "json")]
+impl IntoValue for serde_json::Map<String, serde_json::Value> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        json_map_to_v8(scope, self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        into::{IntoObject, IntoValue},
+        self as v8_derive,
+        errors::Error,
+        into::{
+            into_object_array, object_from_pairs, ExtendObject, IntoObject, IntoObjectSkipNone, IntoSorted, IntoValue,
+        },
         setup, TryFromValue,
     };
-    use std::collections::HashMap;
+    use std::{
+        borrow::Cow,
+        collections::HashMap,
+        net::{IpAddr, SocketAddr},
+        time::Duration,
+    };
     use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::IntoValue;
+
+    #[derive(IntoValue)]
+    struct SkippableObject {
+        #[v8(skip_serializing_if_none)]
+        opt: Option<i32>,
+        #[v8(skip_serializing_if = "Vec::is_empty")]
+        list: Vec<i32>,
+        name: Option<String>,
+    }
+
+    #[derive(IntoValue)]
+    #[v8(null_prototype)]
+    struct Dictionary {
+        name: String,
+    }
+
+    #[derive(IntoValue)]
+    struct Identity {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(IntoValue)]
+    struct Audit {
+        created_by: String,
+        #[v8(path = "meta.revision")]
+        revision: u32,
+    }
+
+    #[derive(IntoValue)]
+    struct Scores {
+        #[v8(sort_keys)]
+        by_name: HashMap<String, i32>,
+    }
+
+    #[derive(IntoValue)]
+    struct UnorderedObject {
+        zebra: i32,
+        apple: i32,
+        mango: i32,
+        banana: i32,
+    }
+
+    #[derive(IntoValue)]
+    #[v8(freeze)]
+    struct FrozenObject {
+        name: String,
+    }
 
     #[test]
     #[allow(clippy::cast_possible_wrap)]
@@ -288,6 +673,129 @@ mod tests {
         assert_eq!(map.get("one"), Some(&"1".to_string()));
     }
 
+    #[test]
+    fn can_convert_cow_keyed_maps() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let owned = "two".to_string();
+        let map: HashMap<Cow<'_, str>, i32> = [(Cow::Borrowed("one"), 1), (Cow::Owned(owned), 2)].into();
+
+        // as a JS Map
+        let map_value = map.clone().into_value(scope);
+        assert!(map_value.is_map());
+        let round_trip = HashMap::<String, i32>::try_from_value(&map_value, scope).expect("Expected a map");
+        assert_eq!(round_trip.len(), 2);
+        assert_eq!(round_trip.get("one"), Some(&1));
+        assert_eq!(round_trip.get("two"), Some(&2));
+
+        // as a JS Object
+        let obj_value = map.into_object(scope);
+        assert!(obj_value.is_object() && !obj_value.is_map());
+        let round_trip = HashMap::<String, i32>::try_from_value(&obj_value, scope).expect("Expected a map");
+        assert_eq!(round_trip.len(), 2);
+        assert_eq!(round_trip.get("one"), Some(&1));
+        assert_eq!(round_trip.get("two"), Some(&2));
+    }
+
+    #[test]
+    fn should_omit_skipped_fields() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let obj = SkippableObject {
+            opt: None,
+            list: vec![],
+            name: None,
+        };
+        let obj_value = obj.into_value(scope);
+        let object = obj_value.to_object(scope).expect("Expected an object");
+
+        // skipped fields are absent, not null
+        let js_key = v8::String::new(scope, "opt").unwrap().into();
+        assert_eq!(object.has(scope, js_key), Some(false));
+        let js_key = v8::String::new(scope, "list").unwrap().into();
+        assert_eq!(object.has(scope, js_key), Some(false));
+
+        // fields without the attribute are still set to null
+        let js_key = v8::String::new(scope, "name").unwrap().into();
+        assert_eq!(object.has(scope, js_key), Some(true));
+        assert!(object.get(scope, js_key).unwrap().is_null());
+
+        // present values are serialized as usual
+        let obj = SkippableObject {
+            opt: Some(42),
+            list: vec![1],
+            name: None,
+        };
+        let obj_value = obj.into_value(scope);
+        let map = HashMap::<String, String>::try_from_value(&obj_value, scope).expect("Expected a map");
+        assert_eq!(map.get("opt"), Some(&"42".to_string()));
+        assert_eq!(map.get("list"), Some(&"1".to_string()));
+    }
+
+    #[test]
+    fn can_convert_socket_addresses() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let addr: SocketAddr = "[2001:db8::1]:8080".parse().unwrap();
+        let addr_value = addr.into_value(scope);
+        assert!(addr_value.is_string());
+        assert_eq!(addr_value.to_rust_string_lossy(scope), "[2001:db8::1]:8080");
+
+        let round_trip = SocketAddr::try_from_value(&addr_value, scope).expect("Expected a socket address");
+        assert_eq!(round_trip, addr);
+        assert_eq!(round_trip.port(), 8080);
+
+        let ip = IpAddr::try_from_value(&"2001:db8::1".to_string().into_value(scope), scope).unwrap();
+        assert_eq!(ip, addr.ip());
+
+        let invalid = "not an address".to_string().into_value(scope);
+        let err = SocketAddr::try_from_value(&invalid, scope).expect_err("Expected an invalid address");
+        assert!(matches!(err, Error::InvalidAddress(value) if value == "not an address"));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn can_convert_durations() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let duration_value = Duration::from_millis(1500).into_value(scope);
+        assert!(duration_value.is_number());
+        assert_eq!(duration_value.number_value(scope), Some(1500.0));
+        let duration = Duration::try_from_value(&duration_value, scope).expect("Expected a duration");
+        assert_eq!(duration, Duration::from_millis(1500));
+
+        // sub-millisecond precision is kept in the fraction
+        let duration_value = Duration::from_micros(1500).into_value(scope);
+        assert_eq!(duration_value.number_value(scope), Some(1.5));
+        let duration = Duration::try_from_value(&duration_value, scope).expect("Expected a duration");
+        assert_eq!(duration, Duration::from_micros(1500));
+
+        // negative durations don't exist
+        let negative = (-1.0).into_value(scope);
+        let err = Duration::try_from_value(&negative, scope).expect_err("Expected an out of range error");
+        assert!(matches!(err, Error::OutOfRange));
+    }
+
     #[cfg(feature = "json")]
     #[test]
     fn can_convert_json_into_a_js_object() {
@@ -321,4 +829,244 @@ mod tests {
         assert_eq!(map.get("age"), Some(&"30".to_string()));
         assert_eq!(map.get("very_large_number"), Some(&MAX_JS_UINT.to_string()));
     }
+
+    #[test]
+    fn can_convert_into_a_frozen_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = FrozenObject {
+            name: "John".to_string(),
+        }
+        .into_value(scope);
+        let js_key = v8::String::new(scope, "obj").unwrap().into();
+        context.global(scope).set(scope, js_key, object);
+
+        let frozen = setup::eval(scope, "Object.isFrozen(obj)");
+        assert!(frozen.is_true());
+
+        // a write is ignored in sloppy mode, and throws in strict mode
+        let name = setup::eval(scope, "obj.name = 'Jane'; obj.extra = 1; obj.name");
+        assert_eq!(name.to_rust_string_lossy(scope), "John");
+        let rejected = setup::eval(
+            scope,
+            "(() => { 'use strict'; try { obj.name = 'Jane'; return false; } catch (e) { return e instanceof TypeError; } })()",
+        );
+        assert!(rejected.is_true());
+    }
+
+    #[test]
+    fn should_set_properties_in_field_declaration_order() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = UnorderedObject {
+            zebra: 1,
+            apple: 2,
+            mango: 3,
+            banana: 4,
+        }
+        .into_value(scope);
+
+        let names = object
+            .to_object(scope)
+            .unwrap()
+            .get_own_property_names(scope, v8::GetPropertyNamesArgs::default())
+            .unwrap();
+        let names = Vec::<String>::try_from_value(&names.into(), scope).unwrap();
+        assert_eq!(names, vec!["zebra", "apple", "mango", "banana"]);
+
+        let keys = setup::eval(scope, "Object.keys");
+        let keys = v8::Local::<v8::Function>::try_from(keys).unwrap();
+        let receiver = v8::undefined(scope).into();
+        let names = keys.call(scope, receiver, &[object]).unwrap();
+        let names = Vec::<String>::try_from_value(&names, scope).unwrap();
+        assert_eq!(names, vec!["zebra", "apple", "mango", "banana"]);
+    }
+
+    #[test]
+    fn can_skip_none_values_of_a_hashmap_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let map: HashMap<String, Option<i32>> = [("one".to_string(), Some(1)), ("none".to_string(), None)].into();
+
+        let object = map.clone().into_object(scope).to_object(scope).unwrap();
+        let key = v8::String::new(scope, "none").unwrap().into();
+        assert!(object.has_own_property(scope, key).unwrap());
+
+        let object = map.into_object_skip_none(scope).to_object(scope).unwrap();
+        let key = v8::String::new(scope, "none").unwrap().into();
+        assert!(!object.has_own_property(scope, key).unwrap());
+        let key = v8::String::new(scope, "one").unwrap().into();
+        let one = object.get(scope, key).unwrap();
+        assert_eq!(one.int32_value(scope), Some(1));
+    }
+
+    #[test]
+    fn can_write_structs_into_an_existing_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let object = v8::Object::new(scope);
+        Identity {
+            id: 7,
+            name: "report".to_string(),
+        }
+        .write_into(object, scope);
+        Audit {
+            created_by: "admin".to_string(),
+            revision: 3,
+        }
+        .write_into(object, scope);
+
+        let js_key = v8::String::new(scope, "object").unwrap().into();
+        context.global(scope).set(scope, js_key, object.into());
+        let json = setup::eval(scope, "JSON.stringify(object)");
+        assert_eq!(
+            json.to_rust_string_lossy(scope),
+            r#"{"id":7,"name":"report","created_by":"admin","meta":{"revision":3}}"#
+        );
+    }
+
+    #[test]
+    fn can_sort_the_keys_of_a_hashmap() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let map: HashMap<String, i32> = ["delta", "alpha", "charlie", "echo", "bravo"]
+            .into_iter()
+            .zip(1..)
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+
+        let object = map.clone().into_object_sorted(scope);
+        context.global(scope).set(scope, js_key, object);
+        let keys = setup::eval(scope, "Object.keys(value).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "alpha,bravo,charlie,delta,echo");
+
+        let js_map = map.clone().into_value_sorted(scope);
+        assert!(js_map.is_map());
+        context.global(scope).set(scope, js_key, js_map);
+        let keys = setup::eval(scope, "[...value.keys()].join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "alpha,bravo,charlie,delta,echo");
+
+        let scores = Scores { by_name: map }.into_value(scope);
+        context.global(scope).set(scope, js_key, scores);
+        let keys = setup::eval(scope, "[...value.by_name.keys()].join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "alpha,bravo,charlie,delta,echo");
+    }
+
+    #[test]
+    fn can_convert_into_an_array_of_js_objects() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let items: Vec<HashMap<String, i32>> = vec![[("one".to_string(), 1)].into(), [("two".to_string(), 2)].into()];
+        let value = into_object_array(items, scope);
+        assert!(value.is_array());
+
+        let array: v8::Local<v8::Array> = value.try_into().unwrap();
+        assert_eq!(array.length(), 2);
+
+        let first = array.get_index(scope, 0).unwrap();
+        assert!(first.is_object() && !first.is_map());
+
+        let items = Vec::<HashMap<String, i32>>::try_from_value(&value, scope).unwrap();
+        assert_eq!(items[0].get("one"), Some(&1));
+        assert_eq!(items[1].get("two"), Some(&2));
+    }
+
+    #[test]
+    fn can_convert_an_option_by_reference() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let some = Some(42);
+        let value = (&some).into_value(scope);
+        assert_eq!(i32::try_from_value(&value, scope).unwrap(), 42);
+
+        let none: Option<String> = None;
+        let value = (&none).into_value(scope);
+        assert!(value.is_null());
+
+        // the options are still owned here
+        assert_eq!(some, Some(42));
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn can_convert_into_an_object_without_a_prototype() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = Dictionary {
+            name: "toString".to_string(),
+        }
+        .into_value(scope);
+        let js_key = v8::String::new(scope, "dictionary").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+
+        let result = setup::eval(
+            scope,
+            "Object.getPrototypeOf(dictionary) === null && dictionary.toString === undefined && dictionary.name",
+        );
+        assert_eq!(result.to_rust_string_lossy(scope), "toString");
+    }
+
+    #[test]
+    fn can_convert_pairs_into_an_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let pairs = (1..=6).filter(|n| n % 2 == 0).map(|n| (format!("key{n}"), n * 10));
+        let value = object_from_pairs(pairs, scope);
+        assert!(value.is_object() && !value.is_map());
+
+        let js_key = v8::String::new(scope, "value").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let keys = setup::eval(scope, "Object.keys(value).join()");
+        assert_eq!(keys.to_rust_string_lossy(scope), "key2,key4,key6");
+
+        let values = HashMap::<String, i32>::try_from_value(&value, scope).unwrap();
+        assert_eq!(values.get("key4"), Some(&40));
+        assert_eq!(values.len(), 3);
+    }
 }
@@ -0,0 +1,73 @@
+//! Conversions for the ipnetwork `IpNetwork`, as its CIDR string form.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_string,
+    IntoValue, TryFromValue,
+};
+use ::ipnetwork::IpNetwork;
+
+impl IntoValue for IpNetwork {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.to_string().into_value(scope)
+    }
+}
+
+/// Like `IpAddr`, the string representation of the JS value is parsed; an address without a prefix, e.g.
+/// `"10.0.0.1"`, is a network of that single address.
+impl TryFromValue for IpNetwork {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        value.parse().map_err(|_| Error::InvalidNetwork(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::ipnetwork::IpNetwork;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_network() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let network: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        let value = network.into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(value.to_rust_string_lossy(scope), "10.0.0.0/8");
+        let result = IpNetwork::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, network);
+        assert_eq!(result.prefix(), 8);
+
+        let network: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let value = network.into_value(scope);
+        assert_eq!(value.to_rust_string_lossy(scope), "2001:db8::/32");
+        let result = IpNetwork::try_from_value(&value, scope).unwrap();
+        assert!(result.is_ipv6());
+        assert_eq!(result, network);
+    }
+
+    #[test]
+    fn rejects_a_malformed_network() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'10.0.0.0/33'");
+        let err = IpNetwork::try_from_value(&value, scope).expect_err("expected an invalid network");
+        assert!(matches!(err, Error::InvalidNetwork(network) if network == "10.0.0.0/33"));
+
+        let value = setup::eval(scope, "'not a network'");
+        let err = IpNetwork::try_from_value(&value, scope).expect_err("expected an invalid network");
+        assert!(matches!(err, Error::InvalidNetwork(_)));
+    }
+}
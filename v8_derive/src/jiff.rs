@@ -0,0 +1,129 @@
+//! Conversions for the `jiff` `Timestamp` and `Zoned`.
+//!
+//! A `Timestamp` is an instant, so it is converted as a JS `Date` object. A `Zoned` also holds its time zone,
+//! which a `Date` cannot, so it is converted as an RFC 9557 string instead, e.g.
+//! `"2024-03-01T12:30:00-05:00[America/New_York]"`.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_string,
+    IntoValue, TryFromValue,
+};
+use ::jiff::{Timestamp, Zoned};
+
+const NANOS_PER_MILLI: i128 = 1_000_000;
+
+/// JS dates have millisecond precision, so the nanoseconds below a millisecond are rounded down, like the `time`
+/// and `chrono` conversions do, e.g. both `1.9ms` and `1.1ms` become `1ms`, and `-1.9ms` becomes `-2ms`.
+impl IntoValue for Timestamp {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        // a timestamp is within ±8.2e15 ms, which a double holds exactly
+        #[allow(clippy::cast_precision_loss)]
+        let millis = self.as_nanosecond().div_euclid(NANOS_PER_MILLI) as f64;
+        match v8::Date::new(scope, millis) {
+            Some(date) => date.into(),
+            None => v8::undefined(scope).into(),
+        }
+    }
+}
+
+impl TryFromValue for Timestamp {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, _scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if !input.is_date() {
+            return Err(Error::ExpectedDate);
+        }
+
+        let date: v8::Local<v8::Date> = input.try_cast()?;
+        let millis = date.value_of();
+
+        // an invalid date has a NaN time value
+        if !millis.is_finite() {
+            return Err(Error::OutOfRange);
+        }
+
+        // the time value of a JS date is always integral, and within ±8.64e15 ms
+        #[allow(clippy::cast_possible_truncation)]
+        Timestamp::from_millisecond(millis as i64).map_err(|_| Error::OutOfRange)
+    }
+}
+
+/// Keeps the full nanosecond precision, as well as the time zone.
+impl IntoValue for Zoned {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.to_string().into_value(scope)
+    }
+}
+
+impl TryFromValue for Zoned {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        value.parse().map_err(|_| Error::InvalidDate(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::jiff::{Timestamp, Zoned};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_timestamp() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let timestamp: Timestamp = "2024-03-01T12:30:00.250Z".parse().unwrap();
+        let value = timestamp.into_value(scope);
+        assert!(value.is_date());
+        assert_eq!(Timestamp::try_from_value(&value, scope).unwrap(), timestamp);
+
+        // the nanoseconds below a millisecond are rounded down
+        let precise = Timestamp::new(1_709_296_200, 250_999_999).unwrap();
+        let value = precise.into_value(scope);
+        assert_eq!(value.number_value(scope), Some(1_709_296_200_250.0));
+        assert_eq!(Timestamp::try_from_value(&value, scope).unwrap(), timestamp);
+
+        // also before 1970, so -1.9ms becomes -2ms
+        let value = Timestamp::from_nanosecond(-1_900_000).unwrap().into_value(scope);
+        assert_eq!(value.number_value(scope), Some(-2.0));
+
+        let value = setup::eval(scope, "new Date(Date.UTC(1969, 11, 31, 23, 59, 59))");
+        assert_eq!(
+            Timestamp::try_from_value(&value, scope).unwrap(),
+            Timestamp::from_second(-1).unwrap()
+        );
+
+        let value = setup::eval(scope, "new Date(NaN)");
+        let err = Timestamp::try_from_value(&value, scope).expect_err("expected an invalid date");
+        assert!(matches!(err, Error::OutOfRange));
+    }
+
+    #[test]
+    fn can_convert_a_zoned_datetime() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let zoned: Zoned = "2024-03-01T12:30:00.000000123+02:00[+02:00]".parse().unwrap();
+        let value = zoned.clone().into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(
+            value.to_rust_string_lossy(scope),
+            "2024-03-01T12:30:00.000000123+02:00[+02:00]"
+        );
+        let result = Zoned::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, zoned);
+        assert_eq!(result.offset(), zoned.offset());
+
+        let value = setup::eval(scope, "'2024-03-01'");
+        let err = Zoned::try_from_value(&value, scope).expect_err("expected an invalid date");
+        assert!(matches!(err, Error::InvalidDate(date) if date == "2024-03-01"));
+    }
+}
@@ -0,0 +1,187 @@
+//! A dynamically typed JS value, for data whose shape is not known up front.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_string,
+    IntoValue, TryFromValue,
+};
+
+/// An owned JS value; unlike `serde_json::Value` it needs no extra dependency, and it keeps a `BigInt` apart from
+/// a number.
+///
+/// A JS object keeps the order of its own enumerable properties.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsValue {
+    /// `null`, and also `undefined`
+    Null,
+    Bool(bool),
+    Number(f64),
+    BigInt(i128),
+    Str(String),
+    Array(Vec<JsValue>),
+    Object(Vec<(String, JsValue)>),
+}
+
+impl TryFromValue for JsValue {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        match () {
+            () if input.is_null_or_undefined() => Ok(Self::Null),
+            () if input.is_boolean() => Ok(Self::Bool(input.boolean_value(scope))),
+            () if input.is_big_int() => try_as_i128(*input).map(Self::BigInt),
+            () if input.is_number() => input.number_value(scope).map(Self::Number).ok_or(Error::ExpectedF64),
+            () if input.is_string() => try_as_string(input, scope).map(Self::Str),
+            () if input.is_array() => Vec::<JsValue>::try_from_value(input, scope).map(Self::Array),
+            () if input.is_object() => try_as_entries(*input, scope).map(Self::Object),
+            () => Err(Error::UnsupportedValueType),
+        }
+    }
+}
+
+/// Reads a `BigInt` that fits in an `i128`
+fn try_as_i128(input: v8::Local<'_, v8::Value>) -> Result<i128> {
+    let big_int: v8::Local<v8::BigInt> = input.try_cast()?;
+    if big_int.word_count() > 2 {
+        return Err(Error::OutOfRange);
+    }
+
+    let mut words = [0; 2];
+    let (negative, words) = big_int.to_words_array(&mut words);
+    let magnitude = words
+        .iter()
+        .rev()
+        .fold(0_u128, |magnitude, word| (magnitude << 64) | u128::from(*word));
+
+    if negative {
+        0_i128.checked_sub_unsigned(magnitude).ok_or(Error::OutOfRange)
+    } else {
+        i128::try_from(magnitude).map_err(|_| Error::OutOfRange)
+    }
+}
+
+/// Reads the own enumerable properties of an object, in property order
+fn try_as_entries(input: v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Vec<(String, JsValue)>> {
+    let object: v8::Local<v8::Object> = input.try_cast()?;
+    let args = v8::GetPropertyNamesArgs {
+        mode: v8::KeyCollectionMode::OwnOnly,
+        ..v8::GetPropertyNamesArgs::default()
+    };
+    let Some(names) = object.get_property_names(scope, args) else {
+        return Err(Error::FailedToGetPropertyNames);
+    };
+
+    let mut entries = Vec::with_capacity(names.length() as usize);
+    for index in 0..names.length() {
+        let Some(key) = names.get_index(scope, index) else {
+            return Err(Error::FailedToGetPropertyNames);
+        };
+        let Some(value) = object.get(scope, key) else {
+            return Err(Error::FieldNotFound(key.to_rust_string_lossy(scope)));
+        };
+
+        let key = try_as_string(&key, scope)?;
+        entries.push((key, JsValue::try_from_value(&value, scope)?));
+    }
+
+    Ok(entries)
+}
+
+impl IntoValue for JsValue {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        match self {
+            Self::Null => v8::null(scope).into(),
+            Self::Bool(value) => value.into_value(scope),
+            Self::Number(value) => value.into_value(scope),
+            Self::BigInt(value) => {
+                let magnitude = value.unsigned_abs();
+                #[allow(clippy::cast_possible_truncation)]
+                let words = [magnitude as u64, (magnitude >> 64) as u64];
+                v8::BigInt::new_from_words(scope, value < 0, &words).unwrap().into()
+            }
+            Self::Str(value) => value.into_value(scope),
+            Self::Array(values) => values.into_value(scope),
+            Self::Object(entries) => {
+                let object = v8::Object::new(scope);
+                for (key, value) in entries {
+                    let js_key = key.into_value(scope);
+                    let js_val = value.into_value(scope);
+                    object.set(scope, js_key, js_val);
+                }
+                object.into()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsValue;
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_each_kind_of_value() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let values = [
+            JsValue::Null,
+            JsValue::Bool(true),
+            JsValue::Number(1.5),
+            JsValue::BigInt(i128::MIN),
+            JsValue::BigInt(-5),
+            JsValue::BigInt(i128::MAX),
+            JsValue::Str("text ✓".to_string()),
+            JsValue::Array(vec![JsValue::Number(1.0), JsValue::Null]),
+            JsValue::Object(vec![
+                ("name".to_string(), JsValue::Str("nested".to_string())),
+                (
+                    "inner".to_string(),
+                    JsValue::Object(vec![("count".to_string(), JsValue::BigInt(1 << 70))]),
+                ),
+            ]),
+        ];
+
+        for expected in values {
+            let value = expected.clone().into_value(scope);
+            assert_eq!(JsValue::try_from_value(&value, scope).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn can_read_a_js_value() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "({ b: 2n ** 64n, a: [undefined, 'x'], c: { d: false } })");
+        assert_eq!(
+            JsValue::try_from_value(&value, scope).unwrap(),
+            JsValue::Object(vec![
+                ("b".to_string(), JsValue::BigInt(1 << 64)),
+                (
+                    "a".to_string(),
+                    JsValue::Array(vec![JsValue::Null, JsValue::Str("x".to_string())])
+                ),
+                (
+                    "c".to_string(),
+                    JsValue::Object(vec![("d".to_string(), JsValue::Bool(false))])
+                ),
+            ])
+        );
+
+        let value = setup::eval(scope, "2n ** 127n");
+        let err = JsValue::try_from_value(&value, scope).expect_err("expected a BigInt out of range");
+        assert!(matches!(err, Error::OutOfRange));
+
+        let value = setup::eval(scope, "Symbol('unsupported')");
+        let err = JsValue::try_from_value(&value, scope).expect_err("expected an unsupported value");
+        assert!(matches!(err, Error::UnsupportedValueType));
+    }
+}
@@ -4,11 +4,78 @@ use crate::{
 };
 use v8::{Local, PinScope, Value};
 
+/// Which properties of a JS object are converted into the JSON object
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonProperties {
+    /// The own enumerable properties, like `JSON.stringify`
+    #[default]
+    OwnEnumerable,
+    /// The enumerable properties, including the ones inherited through the prototype chain
+    WithPrototypes,
+    /// The own properties, including the non-enumerable ones.
+    ///
+    /// This is not combined with the prototype chain, as that would also pick up the built-ins of
+    /// `Object.prototype`, like `constructor`.
+    WithNonEnumerable,
+}
+
+impl JsonProperties {
+    fn property_names_args(self) -> v8::GetPropertyNamesArgs {
+        // JSON keys are strings
+        self.names_args(v8::PropertyFilter::SKIP_SYMBOLS)
+    }
+
+    /// The same properties, keyed by a symbol instead of a string
+    fn symbol_names_args(self) -> v8::GetPropertyNamesArgs {
+        self.names_args(v8::PropertyFilter::SKIP_STRINGS)
+    }
+
+    fn names_args(self, key_filter: v8::PropertyFilter) -> v8::GetPropertyNamesArgs {
+        let (mode, property_filter) = match self {
+            Self::OwnEnumerable => (v8::KeyCollectionMode::OwnOnly, v8::PropertyFilter::ONLY_ENUMERABLE),
+            Self::WithPrototypes => (
+                v8::KeyCollectionMode::IncludePrototypes,
+                v8::PropertyFilter::ONLY_ENUMERABLE,
+            ),
+            Self::WithNonEnumerable => (v8::KeyCollectionMode::OwnOnly, v8::PropertyFilter::ALL_PROPERTIES),
+        };
+
+        v8::GetPropertyNamesArgs {
+            mode,
+            property_filter: property_filter | key_filter,
+            ..v8::GetPropertyNamesArgs::default()
+        }
+    }
+}
+
+/// Converts a JS value into a JSON value, choosing which properties of the (nested) objects are converted.
+///
+/// Cycles are not detected; note that with `JsonProperties::WithNonEnumerable` a function and the
+/// `constructor` of its `prototype` form one.
+///
+/// # Errors
+/// In case of conversion errors, or if the value is not supported, an error is returned.
+pub fn try_as_json_with(
+    input: &Local<'_, Value>,
+    scope: &mut PinScope<'_, '_>,
+    properties: JsonProperties,
+) -> Result<serde_json::Value> {
+    to_json_value(scope, *input, properties)
+}
+
 /// Convert a V8 Object to a JSON Value
 ///
 /// # Errors
 /// In case of conversion errors, or if the value is not supported, an error is returned.
 pub(crate) fn v8_to_json_value(scope: &mut PinScope<'_, '_>, value: Local<Value>) -> Result<serde_json::Value> {
+    to_json_value(scope, value, JsonProperties::default())
+}
+
+fn to_json_value(
+    scope: &mut PinScope<'_, '_>,
+    value: Local<Value>,
+    properties: JsonProperties,
+) -> Result<serde_json::Value> {
     match () {
         () if value.is_string() => {
             let value = String::try_from_value(&value, scope)?;
@@ -22,49 +89,142 @@ pub(crate) fn v8_to_json_value(scope: &mut PinScope<'_, '_>, value: Local<Value>
             let value = u32::try_from_value(&value, scope)?;
             Ok(serde_json::Value::from(value))
         }
-        () if value.is_big_int() => {
-            let value = i64::try_from_value(&value, scope)?;
-            Ok(serde_json::Value::from(value))
-        }
+        () if value.is_big_int() => bigint_to_json(scope, value),
         () if value.is_number() => {
             let value = f64::try_from_value(&value, scope)?;
-            Ok(serde_json::Value::from(value))
+            Ok(number_to_json(value))
         }
         () if value.is_boolean() => {
             let value = bool::try_from_value(&value, scope)?;
             Ok(serde_json::Value::from(value))
         }
         () if value.is_null() => Ok(serde_json::Value::Null),
-        () if value.is_array() => v8_array_to_json(scope, value),
-        () if value.is_object() => v8_object_to_json(scope, value),
+        () if value.is_array() => v8_array_to_json(scope, value, properties),
+        () if value.is_object() => v8_object_to_json(scope, value, properties),
         () => Err(Error::UnsupportedValueType),
     }
 }
 
-fn v8_object_to_json(scope: &mut PinScope<'_, '_>, value: Local<Value>) -> Result<serde_json::Value> {
+/// JS has a single number type, so integral doubles (e.g. `2 ** 40`) are emitted as integer JSON numbers
+/// when they fit in an `i64`; all other numbers are emitted as floats.
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+fn number_to_json(value: f64) -> serde_json::Value {
+    if value.fract() == 0.0 && value >= i64::MIN as f64 && value < i64::MAX as f64 {
+        return serde_json::Value::from(value as i64);
+    }
+
+    serde_json::Value::from(value)
+}
+
+/// A `BigInt` is emitted as an `i64` or `u64` JSON number when it fits in either, e.g. `2n ** 64n - 1n`; a larger
+/// one is emitted as a string holding its decimal digits, since a JSON number would lose precision.
+fn bigint_to_json(scope: &mut PinScope<'_, '_>, value: Local<Value>) -> Result<serde_json::Value> {
+    let big_int: Local<v8::BigInt> = value.try_cast()?;
+
+    if let (number, true) = big_int.i64_value() {
+        return Ok(serde_json::Value::from(number));
+    }
+
+    if let (number, true) = big_int.u64_value() {
+        return Ok(serde_json::Value::from(number));
+    }
+
+    Ok(serde_json::Value::String(value.to_rust_string_lossy(scope)))
+}
+
+fn v8_object_to_json(
+    scope: &mut PinScope<'_, '_>,
+    value: Local<Value>,
+    properties: JsonProperties,
+) -> Result<serde_json::Value> {
+    to_json_map(scope, value, properties).map(serde_json::Value::Object)
+}
+
+/// Convert a V8 Object to a JSON Map
+pub(crate) fn v8_object_to_json_map(
+    scope: &mut PinScope<'_, '_>,
+    value: Local<Value>,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    to_json_map(scope, value, JsonProperties::default())
+}
+
+/// Converts the symbol-keyed properties of a JS object into a JSON object keyed by the description of each symbol,
+/// e.g. `{ "meta": 1 }` for `{ [Symbol('meta')]: 1 }`; this complements `try_as_json_with`, which skips them.
+///
+/// The values are converted like `try_as_json_with` does, without their own symbol-keyed properties. A symbol
+/// without a description has the empty key, and of several symbols with the same description the last one wins.
+///
+/// # Errors
+/// `Error::ExpectedObject` when the value is not an object, or the conversion error of a property
+pub fn try_as_json_symbols(
+    input: &Local<'_, Value>,
+    scope: &mut PinScope<'_, '_>,
+    properties: JsonProperties,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    if !input.is_object() {
+        return Err(Error::ExpectedObject);
+    }
+
+    let object: Local<v8::Object> = input.try_cast()?;
+    let Some(symbols) = object.get_property_names(scope, properties.symbol_names_args()) else {
+        return Err(Error::FailedToGetPropertyNames);
+    };
+
+    let length = symbols.length();
+    let mut json_object = serde_json::Map::with_capacity(length as usize);
+    for i in 0..length {
+        let Some(key) = symbols.get_index(scope, i) else {
+            return Err(Error::ExpectedObject);
+        };
+        let symbol: Local<v8::Symbol> = key.try_cast()?;
+        let description = symbol.description(scope);
+        let description = if description.is_undefined() {
+            String::new()
+        } else {
+            description.to_rust_string_lossy(scope)
+        };
+        let Some(value) = object.get(scope, key) else {
+            return Err(Error::ExpectedObject);
+        };
+        let value = to_json_value(scope, value, properties)?;
+        json_object.insert(description, value);
+    }
+    Ok(json_object)
+}
+
+fn to_json_map(
+    scope: &mut PinScope<'_, '_>,
+    value: Local<Value>,
+    properties: JsonProperties,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
     let Some(object) = value.to_object(scope) else {
         return Err(Error::ExpectedObject);
     };
-    let Some(properties) = object.get_property_names(scope, v8::GetPropertyNamesArgs::default()) else {
+    let property_names_args = properties.property_names_args();
+    let Some(property_names) = object.get_property_names(scope, property_names_args) else {
         return Err(Error::FailedToGetPropertyNames);
     };
-    let length = properties.length();
+    let length = property_names.length();
     let mut json_object = serde_json::Map::with_capacity(length as usize);
     for i in 0..length {
-        let Some(key) = properties.get_index(scope, i) else {
+        let Some(key) = property_names.get_index(scope, i) else {
             return Err(Error::ExpectedObject);
         };
         let key_str = String::try_from_value(&key, scope)?;
         let Some(value) = object.get(scope, key) else {
             return Err(Error::ExpectedObject);
         };
-        let value = v8_to_json_value(scope, value)?;
+        let value = to_json_value(scope, value, properties)?;
         json_object.insert(key_str, value);
     }
-    Ok(serde_json::Value::Object(json_object))
+    Ok(json_object)
 }
 
-fn v8_array_to_json(scope: &mut PinScope<'_, '_>, value: Local<Value>) -> Result<serde_json::Value> {
+fn v8_array_to_json(
+    scope: &mut PinScope<'_, '_>,
+    value: Local<Value>,
+    properties: JsonProperties,
+) -> Result<serde_json::Value> {
     let Ok(array) = value.try_cast::<v8::Array>() else {
         return Err(Error::ExpectedArray);
     };
@@ -72,7 +232,7 @@ fn v8_array_to_json(scope: &mut PinScope<'_, '_>, value: Local<Value>) -> Result
     let mut json_array = Vec::with_capacity(length as usize);
     for i in 0..length {
         let item = match array.get_index(scope, i) {
-            Some(item) => v8_to_json_value(scope, item)?,
+            Some(item) => to_json_value(scope, item, properties)?,
             None => serde_json::Value::Null,
         };
         json_array.push(item);
@@ -105,14 +265,169 @@ pub(crate) fn json_to_v8<'s>(scope: &mut PinScope<'s, '_>, value: serde_json::Va
             }
             js_array.into()
         }
-        serde_json::Value::Object(obj) => {
-            let js_object = v8::Object::new(scope);
-            for (key, val) in obj {
-                let v8_value = json_to_v8(scope, val);
-                let v8_key = key.into_value(scope);
-                js_object.set(scope, v8_key, v8_value);
-            }
-            js_object.into()
-        }
+        serde_json::Value::Object(obj) => json_map_to_v8(scope, obj),
+    }
+}
+
+// Convert a serde_json::Map to a V8 Object
+pub(crate) fn json_map_to_v8<'s>(
+    scope: &mut PinScope<'s, '_>,
+    map: serde_json::Map<String, serde_json::Value>,
+) -> Local<'s, Value> {
+    let js_object = v8::Object::new(scope);
+    for (key, val) in map {
+        let v8_value = json_to_v8(scope, val);
+        let v8_key = key.into_value(scope);
+        js_object.set(scope, v8_key, v8_value);
+    }
+    js_object.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{try_as_json_symbols, try_as_json_with, JsonProperties};
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn should_preserve_integer_numbers() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_value = setup::eval(scope, "1.0");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert!(json.is_i64());
+        assert_eq!(json, serde_json::json!(1));
+
+        // integral, but outside of the int32 range
+        let js_value = setup::eval(scope, "2 ** 40");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert!(json.is_i64());
+        assert_eq!(json, serde_json::json!(1_099_511_627_776_i64));
+
+        let js_value = setup::eval(scope, "1.5");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert!(json.is_f64());
+        assert_eq!(json, serde_json::json!(1.5));
+    }
+
+    #[test]
+    fn can_convert_a_json_map() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let serde_json::Value::Object(map) = serde_json::json!({
+            "name": "John",
+            "age": 30,
+            "tags": ["a", "b"],
+        }) else {
+            unreachable!()
+        };
+
+        let js_value = map.clone().into_value(scope);
+        assert!(js_value.is_object());
+        let result = serde_json::Map::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(result, map);
+
+        let js_value = setup::eval(scope, "'not an object'");
+        let err = serde_json::Map::try_from_value(&js_value, scope).expect_err("expected an object");
+        assert!(matches!(err, crate::errors::Error::ExpectedObject));
+    }
+
+    #[test]
+    fn can_choose_the_properties_of_an_object() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_value = setup::eval(
+            scope,
+            "const object = Object.create({ inherited: 1 }); \
+             object.own = 2; \
+             Object.defineProperty(object, 'hidden', { value: 3, enumerable: false }); \
+             object",
+        );
+
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(json, serde_json::json!({ "own": 2 }));
+        let json = try_as_json_with(&js_value, scope, JsonProperties::OwnEnumerable).unwrap();
+        assert_eq!(json, serde_json::json!({ "own": 2 }));
+
+        let json = try_as_json_with(&js_value, scope, JsonProperties::WithPrototypes).unwrap();
+        assert_eq!(json, serde_json::json!({ "own": 2, "inherited": 1 }));
+
+        let json = try_as_json_with(&js_value, scope, JsonProperties::WithNonEnumerable).unwrap();
+        assert_eq!(json, serde_json::json!({ "own": 2, "hidden": 3 }));
+    }
+
+    #[test]
+    fn can_read_a_bigint_beyond_i64() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_value = setup::eval(scope, "BigInt(18446744073709551615n)");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(json, serde_json::json!(u64::MAX));
+        assert_eq!(json.as_u64(), Some(u64::MAX));
+
+        let js_value = setup::eval(scope, "-9223372036854775808n");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(json.as_i64(), Some(i64::MIN));
+
+        // beyond u64 the digits are kept in a string
+        let js_value = setup::eval(scope, "({ id: 2n ** 64n, small: 5n })");
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(json, serde_json::json!({ "id": "18446744073709551616", "small": 5 }));
+    }
+
+    #[test]
+    fn can_read_the_symbol_keys_into_a_side_map() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_value = setup::eval(
+            scope,
+            "const object = { name: 'widget', [Symbol('meta')]: { version: 2 } }; \
+             Object.defineProperty(object, Symbol('hidden'), { value: 3, enumerable: false }); \
+             object",
+        );
+
+        let json = serde_json::Value::try_from_value(&js_value, scope).unwrap();
+        assert_eq!(json, serde_json::json!({ "name": "widget" }));
+
+        let symbols = try_as_json_symbols(&js_value, scope, JsonProperties::OwnEnumerable).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(symbols),
+            serde_json::json!({ "meta": { "version": 2 } })
+        );
+
+        let symbols = try_as_json_symbols(&js_value, scope, JsonProperties::WithNonEnumerable).unwrap();
+        assert_eq!(
+            serde_json::Value::Object(symbols),
+            serde_json::json!({ "meta": { "version": 2 }, "hidden": 3 })
+        );
+
+        let js_value = setup::eval(scope, "'not an object'");
+        let err = try_as_json_symbols(&js_value, scope, JsonProperties::OwnEnumerable).expect_err("expected an object");
+        assert!(matches!(err, Error::ExpectedObject));
     }
 }
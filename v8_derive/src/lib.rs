@@ -1,19 +1,97 @@
 #![warn(clippy::pedantic)]
 
-pub use from::TryFromValue;
+pub use class::register_class;
+pub use context::{ConversionContext, IntoValueWithCtx, TryFromValueWithCtx};
+pub use deadline::try_from_value_with_deadline;
+pub use exception::throw_error;
+pub use from::{from_owned, TryFromValue};
+pub use global::{from_global, into_global};
 pub use helpers::*;
 pub use into::IntoValue;
+pub use js_value::JsValue;
+pub use promise::resolve_promise;
+pub use script::eval_to;
+pub use set::JsSet;
+pub use typed_array::{SharedBytes, TypedArray};
 
+pub mod class;
+pub mod context;
+pub mod convert;
+pub mod deadline;
 pub mod errors;
+pub mod exception;
 pub mod from;
+pub mod global;
 
 #[allow(dead_code, clippy::missing_errors_doc)]
 pub mod helpers;
 
+pub mod intern;
 pub mod into;
+pub mod js_value;
+pub mod promise;
+pub mod script;
+pub mod set;
+pub mod typed_array;
+
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+
+#[cfg(feature = "base64")]
+mod base64;
+#[cfg(feature = "base64")]
+pub use crate::base64::Base64;
+
+#[cfg(feature = "bitflags")]
+mod bitflags;
+#[cfg(feature = "bitflags")]
+pub use crate::bitflags::JsFlags;
+
+#[cfg(feature = "bstr")]
+mod bstr;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "compact_str")]
+mod compact_str;
+
+#[cfg(feature = "either")]
+mod either;
+
+#[cfg(feature = "enumset")]
+mod enumset;
+#[cfg(feature = "enumset")]
+pub use crate::enumset::JsEnumBits;
+
+#[cfg(feature = "glam")]
+mod glam;
+
+#[cfg(feature = "half")]
+mod half;
+
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
+
+#[cfg(feature = "jiff")]
+mod jiff;
 
 #[cfg(feature = "json")]
 mod json;
+#[cfg(feature = "json")]
+pub use crate::json::{try_as_json_symbols, try_as_json_with, JsonProperties};
+
+#[cfg(feature = "ordered-float")]
+mod ordered_float;
+
+#[cfg(feature = "semver")]
+mod semver;
+
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "url")]
+mod url;
 
 // re-export v8_derive_macros
 pub extern crate v8_derive_macros as macros;
@@ -0,0 +1,79 @@
+//! Conversions for the `ordered_float` wrappers, which make an `f64` usable as the key of a set or map.
+
+use crate::{errors, helpers::try_as_f64, IntoValue, TryFromValue};
+use ::ordered_float::{NotNan, OrderedFloat};
+
+impl IntoValue for OrderedFloat<f64> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.0.into_value(scope)
+    }
+}
+
+impl TryFromValue for OrderedFloat<f64> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        try_as_f64(input, scope).map(OrderedFloat)
+    }
+}
+
+impl IntoValue for NotNan<f64> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.into_inner().into_value(scope)
+    }
+}
+
+/// `NaN` is rejected, including the `NaN` that a non-numeric JS value, like `undefined`, converts into.
+impl TryFromValue for NotNan<f64> {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> errors::Result<Self> {
+        NotNan::new(try_as_f64(input, scope)?).map_err(|_| errors::Error::NonFiniteNumber)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::ordered_float::{NotNan, OrderedFloat};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_an_ordered_float() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = OrderedFloat(1.5).into_value(scope);
+        assert!(value.is_number());
+        assert_eq!(
+            OrderedFloat::<f64>::try_from_value(&value, scope).unwrap(),
+            OrderedFloat(1.5)
+        );
+
+        let value = setup::eval(scope, "NaN");
+        assert!(OrderedFloat::<f64>::try_from_value(&value, scope).unwrap().is_nan());
+    }
+
+    #[test]
+    fn can_convert_a_not_nan() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let number = NotNan::new(-2.25).unwrap();
+        let value = number.into_value(scope);
+        assert_eq!(NotNan::<f64>::try_from_value(&value, scope).unwrap(), number);
+
+        let value = setup::eval(scope, "Infinity");
+        assert!(NotNan::<f64>::try_from_value(&value, scope).unwrap().is_infinite());
+
+        for source in ["NaN", "undefined", "'abc'"] {
+            let value = setup::eval(scope, source);
+            let err = NotNan::<f64>::try_from_value(&value, scope).expect_err("expected NaN to be rejected");
+            assert!(matches!(err, Error::NonFiniteNumber), "{source}");
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Reading the settled value of a JS `Promise`.
+
+use crate::{
+    errors::{Error, Result},
+    TryFromValue,
+};
+
+/// Runs the pending microtasks, so the promise can settle, and converts its fulfilled value into `T`.
+///
+/// # Errors
+/// `Error::PromiseRejected` with the string representation of the reason when the promise rejected,
+/// `Error::PromisePending` when it is still waiting on something else than a microtask, e.g. a timer,
+/// and `Error::ExpectedPromise` when the value is not a promise
+pub fn resolve_promise<T>(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<T>
+where
+    T: TryFromValue,
+{
+    let Ok(promise) = v8::Local::<v8::Promise>::try_from(*input) else {
+        return Err(Error::ExpectedPromise);
+    };
+
+    scope.perform_microtask_checkpoint();
+
+    match promise.state() {
+        v8::PromiseState::Fulfilled => {
+            let value = promise.result(scope);
+            T::try_from_value(&value, scope)
+        }
+        v8::PromiseState::Rejected => {
+            let reason = promise.result(scope).to_rust_string_lossy(scope);
+            Err(Error::PromiseRejected { reason })
+        }
+        v8::PromiseState::Pending => Err(Error::PromisePending),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, resolve_promise, setup};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_resolve_a_promise() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let promise = setup::eval(scope, "Promise.resolve(42)");
+        assert_eq!(resolve_promise::<i32>(&promise, scope).unwrap(), 42);
+
+        // settles once the microtasks have run
+        let promise = setup::eval(scope, "(async () => 'done')().then((value) => value + '!')");
+        assert_eq!(resolve_promise::<String>(&promise, scope).unwrap(), "done!");
+    }
+
+    #[test]
+    fn reports_unresolved_promises() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let promise = setup::eval(scope, "Promise.reject(new Error('nope'))");
+        let err = resolve_promise::<i32>(&promise, scope).expect_err("expected a rejection");
+        assert!(matches!(err, Error::PromiseRejected { reason } if reason == "Error: nope"));
+
+        let promise = setup::eval(scope, "new Promise(() => {})");
+        let err = resolve_promise::<i32>(&promise, scope).expect_err("expected a pending promise");
+        assert!(matches!(err, Error::PromisePending));
+
+        let value = setup::eval(scope, "42");
+        let err = resolve_promise::<i32>(&value, scope).expect_err("expected a promise");
+        assert!(matches!(err, Error::ExpectedPromise));
+    }
+}
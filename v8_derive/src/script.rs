@@ -0,0 +1,114 @@
+//! Running JS source and converting the completion value.
+
+use crate::{
+    errors::{Error, Result},
+    TryFromValue,
+};
+
+/// Compiles and runs the JS source in the current context, and converts its completion value into `T`.
+///
+/// # Errors
+/// `Error::JsException` when the source fails to compile or throws, with the `stack` property of the thrown
+/// error if it has one, or the conversion error when the completion value cannot be converted
+pub fn eval_to<T>(source: &str, scope: &mut v8::PinScope<'_, '_>) -> Result<T>
+where
+    T: TryFromValue,
+{
+    v8::tc_scope!(let tc, scope);
+
+    let Some(source) = v8::String::new(tc, source) else {
+        return Err(Error::JsException {
+            message: "the source is too long".to_string(),
+            stack: None,
+        });
+    };
+
+    let value = v8::Script::compile(tc, source, None).and_then(|script| script.run(tc));
+    let Some(value) = value else {
+        let message = tc.exception().map_or_else(
+            || "execution terminated".to_string(),
+            |exception| exception.to_rust_string_lossy(tc),
+        );
+        // only an `Error` object has a stack; a thrown string or number does not
+        let stack = tc
+            .stack_trace()
+            .filter(|stack| stack.is_string())
+            .map(|stack| stack.to_rust_string_lossy(tc));
+        return Err(Error::JsException { message, stack });
+    };
+
+    T::try_from_value(&value, tc)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as v8_derive, errors::Error, eval_to, setup};
+    use v8::{ContextOptions, CreateParams};
+    use v8_derive_macros::FromValue;
+
+    #[derive(FromValue)]
+    struct Person {
+        age: i32,
+    }
+
+    #[test]
+    fn can_evaluate_into_a_struct() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let person: Person = eval_to("({ age: 42 })", scope).expect("failed to evaluate");
+        assert_eq!(person.age, 42);
+
+        let sum: i32 = eval_to("[1, 2, 3].reduce((a, b) => a + b)", scope).expect("failed to evaluate");
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn should_map_js_errors() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let err = eval_to::<i32>("throw new Error('boom')", scope).expect_err("expected an exception");
+        assert!(matches!(err, Error::JsException { message, .. } if message == "Error: boom"));
+
+        let err = eval_to::<i32>("({ age: ", scope).expect_err("expected a syntax error");
+        assert!(matches!(err, Error::JsException { message, .. } if message.starts_with("SyntaxError")));
+
+        // the isolate is still usable after an exception
+        let value: i32 = eval_to("1 + 1", scope).expect("failed to evaluate");
+        assert_eq!(value, 2);
+    }
+
+    #[test]
+    fn should_capture_the_stack_of_a_js_error() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let source = "function inner() { throw new TypeError('bad input'); }\nfunction outer() { inner(); }\nouter();";
+        let err = eval_to::<i32>(source, scope).expect_err("expected an exception");
+        let Error::JsException { message, stack } = err else {
+            panic!("expected a JS exception, got {err:?}");
+        };
+        assert_eq!(message, "TypeError: bad input");
+        let stack = stack.expect("expected a stack trace");
+        assert!(stack.starts_with("TypeError: bad input"));
+        assert!(stack.contains("at inner"));
+        assert!(stack.contains("at outer"));
+
+        // a thrown value that is not an error has no stack
+        let err = eval_to::<i32>("throw 'plain'", scope).expect_err("expected an exception");
+        assert!(matches!(err, Error::JsException { message, stack: None } if message == "plain"));
+    }
+}
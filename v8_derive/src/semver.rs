@@ -0,0 +1,67 @@
+//! Conversions for the semver `Version`, as its string form.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_string,
+    IntoValue, TryFromValue,
+};
+use ::semver::Version;
+
+impl IntoValue for Version {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.to_string().into_value(scope)
+    }
+}
+
+/// Like `Url`, the string representation of the JS value is parsed.
+impl TryFromValue for Version {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        Version::parse(&value).map_err(|_| Error::InvalidVersion(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::semver::{Prerelease, Version};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_version() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let version = Version::parse("1.2.3-rc.1+build").unwrap();
+        let value = version.clone().into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(value.to_rust_string_lossy(scope), "1.2.3-rc.1+build");
+
+        let result = Version::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, version);
+        assert_eq!(result.pre, Prerelease::new("rc.1").unwrap());
+        assert_eq!(result.build.as_str(), "build");
+    }
+
+    #[test]
+    fn rejects_a_malformed_version() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'1.2'");
+        let err = Version::try_from_value(&value, scope).expect_err("expected an invalid version");
+        assert!(matches!(err, Error::InvalidVersion(version) if version == "1.2"));
+
+        let value = setup::eval(scope, "'v1.2.3'");
+        let err = Version::try_from_value(&value, scope).expect_err("expected an invalid version");
+        assert!(matches!(err, Error::InvalidVersion(_)));
+    }
+}
@@ -0,0 +1,76 @@
+//! Conversions between a `Vec` and a JS `Set`.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_vec,
+    IntoValue, TryFromValue,
+};
+
+/// A `Vec` that is converted to and from a JS `Set`, instead of a plain array.
+///
+/// The elements keep their insertion order; duplicates collapse into one element of the `Set`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsSet<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for JsSet<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> IntoValue for JsSet<T>
+where
+    T: IntoValue,
+{
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let set = v8::Set::new(scope);
+        for value in self.0 {
+            let value = value.into_value(scope);
+            set.add(scope, value);
+        }
+
+        set.into()
+    }
+}
+
+impl<T> TryFromValue for JsSet<T>
+where
+    T: TryFromValue,
+{
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let set: v8::Local<v8::Set> = input.try_cast().map_err(|_| Error::ExpectedSet)?;
+        let values = set.as_array(scope).into();
+        try_as_vec(&values, scope).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, JsSet, TryFromValue};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_set() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let set = JsSet(vec!["a".to_string(), "b".to_string(), "a".to_string()]);
+        let value = set.into_value(scope);
+        assert!(value.is_set());
+
+        let result = JsSet::<String>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, JsSet(vec!["a".to_string(), "b".to_string()]));
+
+        let value = setup::eval(scope, "new Set(['x', 'y'])");
+        let result = JsSet::<String>::try_from_value(&value, scope).unwrap();
+        assert_eq!(result.0, vec!["x", "y"]);
+
+        let value = setup::eval(scope, "['x', 'y']");
+        let err = JsSet::<String>::try_from_value(&value, scope).expect_err("expected a set");
+        assert!(matches!(err, Error::ExpectedSet));
+    }
+}
@@ -0,0 +1,140 @@
+//! Conversions for the `time` date types, represented in JS as `Date` objects.
+//!
+//! A JS `Date` is an instant without an offset, so an `OffsetDateTime` is converted as the UTC instant it
+//! represents, and always comes back with a UTC offset. A `Date` is converted as midnight UTC of that day.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use ::time::{Date, OffsetDateTime};
+
+const NANOS_PER_MILLI: i128 = 1_000_000;
+
+/// JS dates have millisecond precision, so the nanoseconds below a millisecond are rounded down, e.g. both
+/// `1.9ms` and `1.1ms` become `1ms`, and `-1.9ms` becomes `-2ms`.
+impl IntoValue for OffsetDateTime {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        #[allow(clippy::cast_precision_loss)]
+        let millis = self.unix_timestamp_nanos().div_euclid(NANOS_PER_MILLI) as f64;
+        match v8::Date::new(scope, millis) {
+            Some(date) => date.into(),
+            None => v8::undefined(scope).into(),
+        }
+    }
+}
+
+impl TryFromValue for OffsetDateTime {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, _scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        if !input.is_date() {
+            return Err(Error::ExpectedDate);
+        }
+
+        let date: v8::Local<v8::Date> = input.try_cast()?;
+        let millis = date.value_of();
+
+        // an invalid date has a NaN time value
+        if !millis.is_finite() {
+            return Err(Error::OutOfRange);
+        }
+
+        // the time value of a JS date is always integral, and within ±8.64e15 ms
+        #[allow(clippy::cast_possible_truncation)]
+        let nanos = millis as i128 * NANOS_PER_MILLI;
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| Error::OutOfRange)
+    }
+}
+
+impl IntoValue for Date {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        self.midnight().assume_utc().into_value(scope)
+    }
+}
+
+impl TryFromValue for Date {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        OffsetDateTime::try_from_value(input, scope).map(OffsetDateTime::date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::time::{Date, Month, OffsetDateTime, UtcOffset};
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn can_convert_an_offset_datetime() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // 2024-03-01T12:30:00.250+02:00
+        let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(1_709_289_000_250_000_000)
+            .unwrap()
+            .to_offset(offset);
+
+        let js_date = datetime.into_value(scope);
+        assert!(js_date.is_date());
+
+        let js_date: v8::Local<v8::Date> = js_date.try_into().unwrap();
+        assert_eq!(js_date.value_of(), 1_709_289_000_250.0);
+
+        // the same instant, normalized to UTC
+        let result = OffsetDateTime::try_from_value(&js_date.into(), scope).unwrap();
+        assert_eq!(result, datetime);
+        assert_eq!(result.offset(), UtcOffset::UTC);
+        assert_eq!(result.hour(), 10);
+
+        // the nanoseconds below a millisecond are rounded down, also before 1970
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(1_900_000).unwrap();
+        let js_date: v8::Local<v8::Date> = datetime.into_value(scope).try_into().unwrap();
+        assert_eq!(js_date.value_of(), 1.0);
+
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(-1_900_000).unwrap();
+        let js_date: v8::Local<v8::Date> = datetime.into_value(scope).try_into().unwrap();
+        assert_eq!(js_date.value_of(), -2.0);
+    }
+
+    #[test]
+    fn can_convert_a_date() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let date = Date::from_calendar_date(2024, Month::March, 1).unwrap();
+        let js_date = date.into_value(scope);
+        let result = Date::try_from_value(&js_date, scope).unwrap();
+        assert_eq!(result, date);
+
+        let js_date = setup::eval(scope, "new Date(Date.UTC(1999, 11, 31, 23, 59))");
+        let result = Date::try_from_value(&js_date, scope).unwrap();
+        assert_eq!(result, Date::from_calendar_date(1999, Month::December, 31).unwrap());
+    }
+
+    #[test]
+    fn should_reject_invalid_dates() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let js_date = setup::eval(scope, "new Date('not a date')");
+        let err = OffsetDateTime::try_from_value(&js_date, scope).expect_err("expected an invalid date");
+        assert!(matches!(err, Error::OutOfRange));
+
+        let js_string = setup::eval(scope, "'2024-03-01'");
+        let err = OffsetDateTime::try_from_value(&js_string, scope).expect_err("expected a date");
+        assert!(matches!(err, Error::ExpectedDate));
+    }
+}
@@ -0,0 +1,281 @@
+//! Conversions between integer vectors and JS typed arrays.
+
+use crate::{
+    errors::{Error, Result},
+    IntoValue, TryFromValue,
+};
+use std::borrow::Cow;
+
+/// A `Vec` that is converted to and from the JS typed array of its element type, instead of a plain array.
+///
+/// | Rust                 | JS            |
+/// |----------------------|---------------|
+/// | `TypedArray<u8>`     | `Uint8Array`  |
+/// | `TypedArray<u16>`    | `Uint16Array` |
+/// | `TypedArray<i16>`    | `Int16Array`  |
+/// | `TypedArray<u32>`    | `Uint32Array` |
+/// | `TypedArray<i32>`    | `Int32Array`  |
+/// | `TypedArray<f16>`    | `Float32Array`, with the `half` feature; also reads a `Float16Array` |
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypedArray<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for TypedArray<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+/// Bytes that are handed over to V8 as a `Uint8Array`, without copying them.
+///
+/// The `Vec` becomes the backing store of the `ArrayBuffer`, and is dropped when V8 garbage collects the last
+/// `ArrayBuffer` that references it; a large buffer therefore stays allocated after its last use in JS until
+/// the next garbage collection. Use `TypedArray` to copy the bytes instead.
+///
+/// A `Vec` with spare capacity is shrunk to its length first, which may move the bytes once.
+///
+/// Converting from a `Uint8Array` copies the bytes of the view only, so a `subarray` of a larger buffer yields
+/// just its own window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SharedBytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for SharedBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl IntoValue for SharedBytes {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        let length = self.0.len();
+        let store = v8::ArrayBuffer::new_backing_store_from_vec(self.0).make_shared();
+        let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+
+        match v8::Uint8Array::new(scope, buffer, 0, length) {
+            Some(array) => array.into(),
+            None => v8::undefined(scope).into(),
+        }
+    }
+}
+
+/// Converts into a `Uint8Array` like `SharedBytes`: the owned bytes are handed over without copying, while
+/// borrowed bytes are copied once, as V8 cannot keep a reference into memory it does not own.
+impl IntoValue for Cow<'_, [u8]> {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        SharedBytes(self.into_owned()).into_value(scope)
+    }
+}
+
+impl TryFromValue for SharedBytes {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        TypedArray::<u8>::try_from_value(input, scope).map(|bytes| Self(bytes.0))
+    }
+}
+
+macro_rules! impl_typed_array {
+    ($($t:ty => $array:ident),*) => {
+        $(
+            impl IntoValue for TypedArray<$t> {
+                fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+                    let length = self.0.len();
+                    let bytes: Vec<u8> = self.0.into_iter().flat_map(<$t>::to_ne_bytes).collect();
+                    let store = v8::ArrayBuffer::new_backing_store_from_vec(bytes).make_shared();
+                    let buffer = v8::ArrayBuffer::with_backing_store(scope, &store);
+
+                    match v8::$array::new(scope, buffer, 0, length) {
+                        Some(array) => array.into(),
+                        None => v8::undefined(scope).into(),
+                    }
+                }
+            }
+
+            impl TryFromValue for TypedArray<$t> {
+                fn try_from_value(input: &v8::Local<'_, v8::Value>, _scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+                    let array: v8::Local<v8::$array> = input
+                        .try_cast()
+                        .map_err(|_| Error::ExpectedTypedArray(stringify!($array)))?;
+
+                    // copies from the byte offset of the view into its buffer
+                    let mut bytes = vec![0; array.byte_length()];
+                    array.copy_contents(&mut bytes);
+
+                    let values = bytes
+                        .chunks_exact(size_of::<$t>())
+                        .map(|chunk| <$t>::from_ne_bytes(chunk.try_into().expect("chunks have the element size")))
+                        .collect();
+                    Ok(Self(values))
+                }
+            }
+        )*
+    };
+}
+
+impl_typed_array! {
+    u8 => Uint8Array,
+    u16 => Uint16Array,
+    i16 => Int16Array,
+    u32 => Uint32Array,
+    i32 => Int32Array
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, SharedBytes, TryFromValue, TypedArray};
+    use std::borrow::Cow;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_typed_arrays() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = TypedArray(vec![0u16, 1, u16::MAX]).into_value(scope);
+        assert!(value.is_uint16_array());
+        assert_eq!(
+            TypedArray::<u16>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![0, 1, u16::MAX])
+        );
+
+        let value = TypedArray(vec![i16::MIN, -1, i16::MAX]).into_value(scope);
+        assert!(value.is_int16_array());
+        assert_eq!(
+            TypedArray::<i16>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![i16::MIN, -1, i16::MAX])
+        );
+
+        let value = TypedArray(vec![0u32, 1, u32::MAX]).into_value(scope);
+        assert!(value.is_uint32_array());
+        assert_eq!(
+            TypedArray::<u32>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![0, 1, u32::MAX])
+        );
+
+        let value = TypedArray(vec![i32::MIN, -1, i32::MAX]).into_value(scope);
+        assert!(value.is_int32_array());
+        assert_eq!(
+            TypedArray::<i32>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![i32::MIN, -1, i32::MAX])
+        );
+
+        let value = TypedArray::<i32>(vec![]).into_value(scope);
+        assert_eq!(
+            TypedArray::<i32>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![])
+        );
+    }
+
+    #[test]
+    fn should_read_typed_arrays_created_in_js() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        // a view into the middle of a larger buffer
+        let value = setup::eval(scope, "new Int16Array(new Int16Array([1, 2, 3, 4]).buffer, 2, 2)");
+        assert_eq!(
+            TypedArray::<i16>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![2, 3])
+        );
+
+        let value = setup::eval(scope, "new Uint32Array([1, 2])");
+        let err = TypedArray::<i32>::try_from_value(&value, scope).expect_err("expected an Int32Array");
+        assert!(matches!(err, Error::ExpectedTypedArray("Int32Array")));
+
+        let value = setup::eval(scope, "[1, 2]");
+        let err = TypedArray::<u16>::try_from_value(&value, scope).expect_err("expected a Uint16Array");
+        assert!(matches!(err, Error::ExpectedTypedArray("Uint16Array")));
+    }
+
+    #[test]
+    fn should_hand_over_shared_bytes_without_copying() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let mut bytes = vec![0u8; 4 * 1024 * 1024];
+        bytes[0] = 1;
+        bytes[4 * 1024 * 1024 - 1] = 2;
+        let pointer = bytes.as_ptr();
+
+        let value = SharedBytes(bytes).into_value(scope);
+        assert!(value.is_uint8_array());
+
+        let array: v8::Local<v8::Uint8Array> = value.try_into().unwrap();
+        assert_eq!(array.byte_length(), 4 * 1024 * 1024);
+        // the typed array points at the memory of the original `Vec`
+        assert_eq!(array.data().cast::<u8>().cast_const(), pointer);
+
+        let js_key = v8::String::new(scope, "bytes").unwrap().into();
+        context.global(scope).set(scope, js_key, value);
+        let last = setup::eval(scope, "bytes[0] + bytes[bytes.length - 1]");
+        assert_eq!(last.int32_value(scope), Some(3));
+    }
+
+    #[test]
+    fn should_read_only_the_window_of_a_subarray() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "new Uint8Array([1, 2, 3, 4, 5, 6, 7, 8]).subarray(2, 5)");
+        let array: v8::Local<v8::Uint8Array> = value.try_into().unwrap();
+        assert_eq!(array.byte_offset(), 2);
+        assert_eq!(array.byte_length(), 3);
+
+        assert_eq!(
+            TypedArray::<u8>::try_from_value(&value, scope).unwrap(),
+            TypedArray(vec![3, 4, 5])
+        );
+        assert_eq!(
+            SharedBytes::try_from_value(&value, scope).unwrap(),
+            SharedBytes(vec![3, 4, 5])
+        );
+
+        let value = setup::eval(scope, "new Uint8Array(new ArrayBuffer(16), 12)");
+        assert_eq!(
+            SharedBytes::try_from_value(&value, scope).unwrap(),
+            SharedBytes(vec![0; 4])
+        );
+    }
+
+    #[test]
+    fn can_convert_borrowed_and_owned_bytes() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let bytes = [1u8, 2, 3];
+        let value = Cow::Borrowed(&bytes[..]).into_value(scope);
+        assert!(value.is_uint8_array());
+        assert_eq!(
+            SharedBytes::try_from_value(&value, scope).unwrap(),
+            SharedBytes(vec![1, 2, 3])
+        );
+
+        let owned = vec![4u8, 5];
+        let pointer = owned.as_ptr();
+        let value = Cow::<[u8]>::Owned(owned).into_value(scope);
+        let array: v8::Local<v8::Uint8Array> = value.try_into().unwrap();
+        // the owned bytes are not copied
+        assert_eq!(array.data().cast::<u8>().cast_const(), pointer);
+        assert_eq!(
+            SharedBytes::try_from_value(&value, scope).unwrap(),
+            SharedBytes(vec![4, 5])
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Conversions for `Url`, as its string form.
+
+use crate::{
+    errors::{Error, Result},
+    helpers::try_as_string,
+    IntoValue, TryFromValue,
+};
+use ::url::Url;
+
+impl IntoValue for Url {
+    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+        String::from(self).into_value(scope)
+    }
+}
+
+/// Like the address types, the string representation of the JS value is parsed.
+impl TryFromValue for Url {
+    fn try_from_value(input: &v8::Local<'_, v8::Value>, scope: &mut v8::PinScope<'_, '_>) -> Result<Self> {
+        let value = try_as_string(input, scope)?;
+        Url::parse(&value).map_err(|_| Error::InvalidUrl(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Error, setup, IntoValue, TryFromValue};
+    use ::url::Url;
+    use v8::{ContextOptions, CreateParams};
+
+    #[test]
+    fn can_convert_a_url() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let url = Url::parse("https://example.com/search?q=v8&page=2#results").unwrap();
+        let value = url.clone().into_value(scope);
+        assert!(value.is_string());
+        assert_eq!(
+            value.to_rust_string_lossy(scope),
+            "https://example.com/search?q=v8&page=2#results"
+        );
+
+        let result = Url::try_from_value(&value, scope).unwrap();
+        assert_eq!(result, url);
+        assert_eq!(result.query(), Some("q=v8&page=2"));
+        assert_eq!(result.fragment(), Some("results"));
+    }
+
+    #[test]
+    fn rejects_a_malformed_url() {
+        setup::setup_test();
+        let isolate = &mut v8::Isolate::new(CreateParams::default());
+        let scope = std::pin::pin!(v8::HandleScope::new(isolate));
+        let scope = &mut scope.init();
+        let context = v8::Context::new(scope, ContextOptions::default());
+        let scope = &mut v8::ContextScope::new(scope, context);
+
+        let value = setup::eval(scope, "'http://exa mple.com'");
+        let err = Url::try_from_value(&value, scope).expect_err("expected an invalid url");
+        assert!(matches!(err, Error::InvalidUrl(url) if url == "http://exa mple.com"));
+
+        let value = setup::eval(scope, "'/relative/path'");
+        let err = Url::try_from_value(&value, scope).expect_err("expected an invalid url");
+        assert!(matches!(err, Error::InvalidUrl(_)));
+    }
+}
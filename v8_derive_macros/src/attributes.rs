@@ -0,0 +1,568 @@
+//! Parsing of the `#[v8(...)]` helper attributes.
+
+/// Attributes that can be placed on the struct itself
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct ContainerAttributes {
+    /// Validator called with the struct, after it was converted from a JS value
+    pub(crate) validate: Option<syn::ExprPath>,
+    /// Convert the struct to and from a positional array, in field declaration order
+    pub(crate) array: bool,
+    /// Key of the property that holds the name of the enum variant
+    pub(crate) discriminant_key: Option<syn::LitStr>,
+    /// Key of the constant property that tags the object of a struct, e.g. to take part in a tagged union
+    pub(crate) tag: Option<syn::LitStr>,
+    /// Value of the `tag` property
+    pub(crate) tag_value: Option<syn::LitStr>,
+    /// Freeze the JS object after converting into it
+    pub(crate) freeze: bool,
+    /// Function returning the handler of the `Proxy` that the JS object is wrapped in
+    pub(crate) proxy: Option<syn::ExprPath>,
+    /// Name of the registered JS class to convert instances of, instead of plain objects
+    pub(crate) class: Option<syn::LitStr>,
+    /// Create the JS object without a prototype, like `Object.create(null)`
+    pub(crate) null_prototype: bool,
+    /// Leave the fields that are missing from the JS object at the default of their type
+    pub(crate) default: bool,
+    /// Casing applied to the names of the fields, or of the variants of an enum
+    pub(crate) rename_all: Option<RenameRule>,
+    /// Function called at runtime with the name of each field, returning the name of its property
+    pub(crate) rename_with: Option<syn::ExprPath>,
+    /// Match the variant names of an enum ignoring ASCII case, when converting from a JS value
+    pub(crate) case_insensitive: bool,
+    /// Convert a struct with a single named field as the value of that field, like a newtype struct
+    pub(crate) transparent: bool,
+    /// Generate a `{Name}Partial` struct and `try_from_value_partial`, which collects the errors of all fields
+    pub(crate) partial: bool,
+    /// Accept JS numbers for the `i64` and `u64` fields only when they hold an integer exactly
+    pub(crate) strict_numbers: bool,
+    /// Where predicates of the `TryFromValue` impl, instead of the inferred bounds on the type parameters
+    pub(crate) from_bound: Option<Vec<syn::WherePredicate>>,
+    /// Where predicates of the `IntoValue` impl, instead of the inferred bounds on the type parameters
+    pub(crate) into_bound: Option<Vec<syn::WherePredicate>>,
+    /// Type of the context that the `TryFromValueWithCtx` and `IntoValueWithCtx` impls take
+    pub(crate) ctx: Option<syn::Type>,
+    /// Some field has the `with_ctx` attribute, so the struct only converts with a context
+    pub(crate) with_ctx_fields: bool,
+}
+
+impl ContainerAttributes {
+    pub(crate) fn from_input(input: &syn::DeriveInput) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("v8")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("validate") {
+                    let path: syn::LitStr = meta.value()?.parse()?;
+                    attributes.validate = Some(path.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("array") {
+                    attributes.array = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("freeze") {
+                    attributes.freeze = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("null_prototype") {
+                    attributes.null_prototype = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("default") {
+                    attributes.default = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("proxy") {
+                    let path: syn::LitStr = meta.value()?.parse()?;
+                    attributes.proxy = Some(path.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("class") {
+                    attributes.class = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("rename_all") {
+                    let rule: syn::LitStr = meta.value()?.parse()?;
+                    attributes.rename_all = Some(RenameRule::from_lit(&rule)?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("bound") {
+                    return attributes.parse_bound(&meta);
+                }
+
+                if meta.path.is_ident("partial") {
+                    attributes.partial = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("transparent") {
+                    attributes.transparent = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("strict_numbers") {
+                    attributes.strict_numbers = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("rename_with") {
+                    let path: syn::LitStr = meta.value()?.parse()?;
+                    attributes.rename_with = Some(path.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("case_insensitive") {
+                    attributes.case_insensitive = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("tag") {
+                    attributes.tag = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("tag_value") {
+                    attributes.tag_value = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("discriminant_key") {
+                    attributes.discriminant_key = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("ctx") {
+                    let ty: syn::LitStr = meta.value()?.parse()?;
+                    attributes.ctx = Some(ty.parse()?);
+                    return Ok(());
+                }
+
+                Err(meta.error("unsupported v8 container attribute"))
+            })?;
+        }
+
+        attributes.with_ctx_fields = has_with_ctx_field(input)?;
+        attributes.check(input)?;
+        Ok(attributes)
+    }
+
+    /// Parses `bound = "..."` for both derives, or `bound(from = "...", into = "...")` for each of them
+    fn parse_bound(&mut self, meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<()> {
+        if meta.input.peek(syn::Token![=]) {
+            let bound = parse_bound(&meta.value()?.parse()?)?;
+            self.from_bound = Some(bound.clone());
+            self.into_bound = Some(bound);
+            return Ok(());
+        }
+
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident("from") {
+                self.from_bound = Some(parse_bound(&nested.value()?.parse()?)?);
+                return Ok(());
+            }
+
+            if nested.path.is_ident("into") {
+                self.into_bound = Some(parse_bound(&nested.value()?.parse()?)?);
+                return Ok(());
+            }
+
+            Err(nested.error("expected `from` or `into`"))
+        })
+    }
+
+    /// Rejects the combinations of attributes that do not fit together, or do not fit the item
+    fn check(&self, input: &syn::DeriveInput) -> syn::Result<()> {
+        if self.rename_all.is_some() && self.rename_with.is_some() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the rename_all and rename_with attributes cannot be combined",
+            ));
+        }
+
+        if self.tag.is_some() != self.tag_value.is_some() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the tag and tag_value attributes must be used together",
+            ));
+        }
+
+        let named_fields =
+            matches!(&input.data, syn::Data::Struct(data) if matches!(data.fields, syn::Fields::Named(_)));
+        if self.tag.is_some() && (!named_fields || self.array || self.transparent) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the tag attribute requires a struct with named fields, without the array or transparent attribute",
+            ));
+        }
+
+        if self.partial && (!named_fields || self.array || self.transparent) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the partial attribute requires a struct with named fields, without the array or transparent \
+                 attribute",
+            ));
+        }
+
+        if self.ctx.is_some() && (!named_fields || self.array || self.transparent) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the ctx attribute requires a struct with named fields, without the array or transparent attribute",
+            ));
+        }
+
+        if self.with_ctx_fields && self.ctx.is_none() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the with_ctx field attribute requires the ctx container attribute",
+            ));
+        }
+
+        if self.with_ctx_fields && self.partial {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the partial attribute is not supported on a struct with with_ctx fields",
+            ));
+        }
+
+        let single_field = matches!(&input.data, syn::Data::Struct(data) if data.fields.len() == 1);
+        if self.transparent && !single_field {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "the transparent attribute requires a struct with exactly one field",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// How the names of the fields of the struct are turned into property names
+    pub(crate) fn field_naming(&self) -> FieldNaming<'_> {
+        FieldNaming {
+            rename_all: self.rename_all,
+            rename_with: self.rename_with.as_ref(),
+        }
+    }
+}
+
+/// The `rename_all` and `rename_with` attributes, as they apply to the fields of a struct; the fields of enum
+/// variants use the default, which keeps their names
+#[derive(Clone, Copy, Default)]
+pub(crate) struct FieldNaming<'a> {
+    pub(crate) rename_all: Option<RenameRule>,
+    pub(crate) rename_with: Option<&'a syn::ExprPath>,
+}
+
+/// Attributes that can be placed on the variants of an enum
+#[derive(Default)]
+pub(crate) struct VariantAttributes {
+    /// Hold the name of an unknown variant in this newtype `String` variant, instead of rejecting it
+    pub(crate) other: bool,
+}
+
+impl VariantAttributes {
+    pub(crate) fn from_variant(variant: &syn::Variant) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("v8")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("other") {
+                    attributes.other = true;
+                    return Ok(());
+                }
+
+                Err(meta.error("unsupported v8 variant attribute"))
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Whether a field of the struct, or of a variant of the enum, has the `with_ctx` attribute
+fn has_with_ctx_field(input: &syn::DeriveInput) -> syn::Result<bool> {
+    let fields: Vec<&syn::Field> = match &input.data {
+        syn::Data::Struct(data) => data.fields.iter().collect(),
+        syn::Data::Enum(data) => data.variants.iter().flat_map(|variant| &variant.fields).collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+
+    for field in fields {
+        if FieldAttributes::from_field(field)?.with_ctx {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// The names of the `numeric_object` attribute, `"0"`, `"1"` and so on, one per element of the tuple field
+fn numeric_object_names(field: &syn::Field, has_fields: bool) -> syn::Result<Vec<syn::LitStr>> {
+    if has_fields {
+        return Err(syn::Error::new_spanned(
+            field,
+            "the numeric_object and fields attributes cannot be combined",
+        ));
+    }
+
+    let ty = crate::helpers::option_type(&field.ty).unwrap_or(&field.ty);
+    let syn::Type::Tuple(tuple) = ty else {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "the numeric_object attribute is only supported on tuple fields",
+        ));
+    };
+
+    Ok((0..tuple.elems.len())
+        .map(|index| syn::LitStr::new(&index.to_string(), proc_macro2::Span::call_site()))
+        .collect())
+}
+
+/// Parses the comma separated where predicates of a `bound` attribute, e.g. `"T: v8_derive::TryFromValue"`
+fn parse_bound(bound: &syn::LitStr) -> syn::Result<Vec<syn::WherePredicate>> {
+    let predicates =
+        bound.parse_with(syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated)?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// The casings of `rename_all`, named like the ones of serde
+#[derive(Clone, Copy)]
+pub(crate) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_lit(rule: &syn::LitStr) -> syn::Result<Self> {
+        match rule.value().as_str() {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(syn::Error::new_spanned(
+                rule,
+                "unsupported rename_all casing, expected one of: lowercase, UPPERCASE, PascalCase, camelCase, \
+                 snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE",
+            )),
+        }
+    }
+
+    /// Renames a variant, which is written in `PascalCase`
+    pub(crate) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_string(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            Self::Snake | Self::ScreamingSnake | Self::Kebab | Self::ScreamingKebab => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                self.apply_to_field(&snake)
+            }
+        }
+    }
+
+    /// Renames a field, which is written in `snake_case`
+    pub(crate) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_string(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal | Self::Camel => {
+                let mut pascal = String::new();
+                let mut capitalize = matches!(self, Self::Pascal);
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// Attributes that can be placed on a struct field
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub(crate) struct FieldAttributes {
+    /// Omit the property from the JS object when the field is `None`
+    pub(crate) skip_serializing_if_none: bool,
+    /// Omit the property from the JS object when the predicate returns `true`
+    pub(crate) skip_serializing_if: Option<syn::ExprPath>,
+    /// Dotted path at which the property is located, e.g. `meta.id`
+    pub(crate) path: Option<syn::LitStr>,
+    /// Leave the holes of a sparse array out of the `Vec`
+    pub(crate) skip_holes: bool,
+    /// Also accept a `BigInt`, or a `Number` or `BigInt` wrapper object, for an `i32` or `u32` field, as long as it fits
+    pub(crate) coerce: bool,
+    /// Convert an integer field to and from a JS string holding the number
+    pub(crate) numeric_string: bool,
+    /// Convert an `i64` or `u64` field into a JS number within the safe integer range, and a `BigInt` beyond it
+    pub(crate) number_if_safe: bool,
+    /// Insert the entries of a `HashMap` field in key order
+    pub(crate) sort_keys: bool,
+    /// Names of the properties that hold the elements of a tuple field, instead of an array
+    pub(crate) fields: Option<Vec<syn::LitStr>>,
+    /// Accept a JS number for an `i64` or `u64` field only when it holds an integer exactly; also set on every
+    /// field by the `strict_numbers` container attribute
+    pub(crate) strict_numbers: bool,
+    /// Convert a `Vec<(K, V)>` field to and from a JS `Map`, keeping the order of its entries
+    pub(crate) preserve_key_order: bool,
+    /// Convert a tuple field to and from an object keyed by the positions, like `fields = ["0", "1"]`
+    pub(crate) numeric_object: bool,
+    /// Fill a `Vec` field from any JS iterable, e.g. a `Set` or a generator, instead of only an array
+    pub(crate) iterable: bool,
+    /// Convert a `Vec<char>` field to and from a single JS string, instead of an array
+    pub(crate) as_string: bool,
+    /// Convert the field with `TryFromValueWithCtx` and `IntoValueWithCtx`, passing on the context of the struct
+    pub(crate) with_ctx: bool,
+}
+
+impl FieldAttributes {
+    pub(crate) fn from_field(field: &syn::Field) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("v8")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip_serializing_if_none") {
+                    attributes.skip_serializing_if_none = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("skip_serializing_if") {
+                    let path: syn::LitStr = meta.value()?.parse()?;
+                    attributes.skip_serializing_if = Some(path.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("path") {
+                    attributes.path = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("skip_holes") {
+                    attributes.skip_holes = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("coerce") {
+                    attributes.coerce = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("numeric_string") {
+                    attributes.numeric_string = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("fields") {
+                    let names: syn::ExprArray = meta.value()?.parse()?;
+                    let names = names
+                        .elems
+                        .iter()
+                        .map(|name| match name {
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(name),
+                                ..
+                            }) => Ok(name.clone()),
+                            _ => Err(syn::Error::new_spanned(name, "expected a string literal")),
+                        })
+                        .collect::<syn::Result<_>>()?;
+                    attributes.fields = Some(names);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("strict_numbers") {
+                    attributes.strict_numbers = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("sort_keys") {
+                    attributes.sort_keys = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("number_if_safe") {
+                    attributes.number_if_safe = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("preserve_key_order") {
+                    attributes.preserve_key_order = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("numeric_object") {
+                    attributes.numeric_object = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("iterable") {
+                    attributes.iterable = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("as_string") {
+                    attributes.as_string = true;
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("with_ctx") {
+                    attributes.with_ctx = true;
+                    return Ok(());
+                }
+
+                Err(meta.error("unsupported v8 field attribute"))
+            })?;
+        }
+
+        if attributes.numeric_object {
+            attributes.fields = Some(numeric_object_names(field, attributes.fields.is_some())?);
+        }
+
+        if attributes.with_ctx && attributes.path.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "the with_ctx and path attributes cannot be combined",
+            ));
+        }
+
+        Ok(attributes)
+    }
+}
@@ -0,0 +1,533 @@
+//! Implementation of the `FromValue` derive
+
+use crate::{
+    attributes::{ContainerAttributes, FieldAttributes, FieldNaming},
+    helpers::{
+        bounded_generics, is_phantom_data, is_unit_enum, js_variant_name, newtype_field, newtype_member, other_variant,
+        quote_field_key, quote_get_element_as, quote_get_field_as, quote_get_field_result, quote_parse_fn,
+    },
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Data;
+
+pub(crate) fn derive(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let identifier = &input.ident;
+    let container_attributes = ContainerAttributes::from_input(input)?;
+
+    let implementation = match &input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => {
+            validate_struct_attributes(identifier, fields, &container_attributes)?;
+
+            if let Some(field) = newtype_field(fields, container_attributes.transparent) {
+                let attributes = field_attributes(field, &container_attributes)?;
+                let parse_fn = quote_parse_fn(&field.ty, &attributes);
+                let member = newtype_member(field);
+                quote! { Self { #member: #parse_fn(input, scope)? } }
+            } else if container_attributes.array {
+                from_array(fields, &container_attributes)?
+            } else {
+                let implementation = from_object(
+                    &quote! { Self },
+                    fields,
+                    &container_attributes,
+                    container_attributes.field_naming(),
+                    None,
+                )?;
+                quote_check_object(implementation, &container_attributes)
+            }
+        }
+        Data::Enum(data) => {
+            if container_attributes.array
+                || container_attributes.class.is_some()
+                || container_attributes.default
+                || container_attributes.rename_with.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    identifier,
+                    "the array, class, default and rename_with attributes are not supported on enums",
+                ));
+            }
+
+            match &container_attributes.discriminant_key {
+                Some(key) => from_enum(key, data, &container_attributes)?,
+                None if is_unit_enum(data) => from_unit_enum(data, &container_attributes)?,
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        identifier,
+                        "enums with fields require the discriminant_key attribute",
+                    ))
+                }
+            }
+        }
+        Data::Union(_) => panic!("Only structs and enums are supported"),
+    };
+
+    let validation = container_attributes.validate.as_ref().map(|validate| {
+        quote! {
+            #validate(&value).map_err(v8_derive::errors::Error::Validation)?;
+        }
+    });
+
+    let generics = bounded_generics(
+        input,
+        &quote! { v8_derive::TryFromValue },
+        container_attributes.from_bound.as_deref(),
+    );
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let partial = container_attributes
+        .partial
+        .then(|| quote_partial(input, &generics, &container_attributes))
+        .transpose()?;
+    let with_ctx = quote_with_ctx(input, &generics, &container_attributes, validation.as_ref())?;
+
+    // the with_ctx fields cannot be converted without a context
+    let try_from_value = (!container_attributes.with_ctx_fields).then(|| {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics v8_derive::TryFromValue for #identifier #type_generics #where_clause {
+                fn try_from_value(
+                    input: &v8::Local<'_, v8::Value>,
+                    scope: &mut v8::PinScope<'_, '_>,
+                ) -> v8_derive::errors::Result<Self>
+                where
+                    Self: Sized {
+                        let value = #implementation;
+                        #validation
+                        Ok(value)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #partial
+        #try_from_value
+        #with_ctx
+    })
+}
+
+/// Check the `tag` property, and that the input is an instance of the registered `class`, before `implementation`
+/// reads the fields of the object
+fn quote_check_object(implementation: TokenStream, container_attributes: &ContainerAttributes) -> TokenStream {
+    let implementation = match (&container_attributes.tag, &container_attributes.tag_value) {
+        (Some(tag), Some(tag_value)) => quote! {
+            {
+                let tag = v8_derive::get_field_as(#tag, input, scope, v8_derive::helpers::try_as_string)?;
+                if tag != #tag_value {
+                    return Err(v8_derive::errors::Error::TagMismatch {
+                        expected: #tag_value.to_string(),
+                        actual: tag,
+                    });
+                }
+                #implementation
+            }
+        },
+        _ => implementation,
+    };
+
+    match &container_attributes.class {
+        Some(class) => quote! {
+            {
+                v8_derive::class::expect_instance(#class, input, scope)?;
+                #implementation
+            }
+        },
+        None => implementation,
+    }
+}
+
+/// Implement `TryFromValueWithCtx` for the context type of the `ctx` attribute, which reads the fields like
+/// `TryFromValue` does and reports each of them to the context
+fn quote_with_ctx(
+    input: &syn::DeriveInput,
+    generics: &syn::Generics,
+    container_attributes: &ContainerAttributes,
+    validation: Option<&TokenStream>,
+) -> syn::Result<Option<TokenStream>> {
+    let (Some(ctx), Data::Struct(syn::DataStruct { fields, .. })) = (&container_attributes.ctx, &input.data) else {
+        return Ok(None);
+    };
+
+    let identifier = &input.ident;
+    let implementation = from_object(
+        &quote! { Self },
+        fields,
+        container_attributes,
+        container_attributes.field_naming(),
+        Some(ctx),
+    )?;
+    let implementation = quote_check_object(implementation, container_attributes);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        impl #impl_generics v8_derive::context::TryFromValueWithCtx<#ctx> for #identifier #type_generics #where_clause {
+            fn try_from_value_with_ctx(
+                input: &v8::Local<'_, v8::Value>,
+                scope: &mut v8::PinScope<'_, '_>,
+                ctx: &mut #ctx,
+            ) -> v8_derive::errors::Result<Self> {
+                let value = #implementation;
+                #validation
+                Ok(value)
+            }
+        }
+    }))
+}
+
+/// Declare the `{Name}Partial` struct of the `partial` attribute, with an `Option` of each field, and implement
+/// `try_from_value_partial`, which reads every field that converts and collects the errors of the others
+fn quote_partial(
+    input: &syn::DeriveInput,
+    generics: &syn::Generics,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<TokenStream> {
+    let Data::Struct(syn::DataStruct { fields, .. }) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "the partial attribute is only supported on structs",
+        ));
+    };
+
+    let identifier = &input.ident;
+    let visibility = &input.vis;
+    let partial = quote::format_ident!("{identifier}Partial");
+    let naming = container_attributes.field_naming();
+    let mut declarations = TokenStream::new();
+    let mut reads = TokenStream::new();
+    let mut members = TokenStream::new();
+
+    for field in fields {
+        let Some(member) = field.ident.as_ref() else {
+            continue;
+        };
+
+        let field_visibility = &field.vis;
+        let ty = &field.ty;
+        members.extend(quote! { #member, });
+
+        if is_phantom_data(field) {
+            declarations.extend(quote! { #field_visibility #member: #ty, });
+            reads.extend(quote! { let #member = ::core::marker::PhantomData; });
+            continue;
+        }
+
+        let attributes = field_attributes(field, container_attributes)?;
+        let key = quote_field_key(member, naming);
+        let result = quote_get_field_result(field, &key, &attributes, container_attributes.default);
+        declarations.extend(quote! { #field_visibility #member: Option<#ty>, });
+        reads.extend(quote! {
+            let #member = match #result {
+                Ok(value) => Some(value),
+                Err(error) => {
+                    errors.push(error);
+                    None
+                }
+            };
+        });
+    }
+
+    let checks = quote_partial_checks(container_attributes);
+    let declared_generics = &input.generics;
+    let declared_where_clause = &input.generics.where_clause;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        /// The fields that converted from the JS value; a field that failed to convert is `None`
+        #visibility struct #partial #declared_generics #declared_where_clause {
+            #declarations
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #identifier #type_generics #where_clause {
+            /// Converts every field that can be converted, instead of stopping at the first error; returns the
+            /// errors of the fields that failed, which are `None` in the partial struct. A `class` or `tag`
+            /// mismatch is returned as the first error, and the fields are still read
+            #visibility fn try_from_value_partial(
+                input: &v8::Local<'_, v8::Value>,
+                scope: &mut v8::PinScope<'_, '_>,
+            ) -> (#partial #type_generics, Vec<v8_derive::errors::Error>) {
+                let mut errors = Vec::new();
+                #checks
+                #reads
+                (#partial { #members }, errors)
+            }
+        }
+    })
+}
+
+/// The `class` and `tag` checks of `quote_check_object`, which push their error instead of returning it
+fn quote_partial_checks(container_attributes: &ContainerAttributes) -> TokenStream {
+    let mut checks = TokenStream::new();
+
+    if let Some(class) = &container_attributes.class {
+        checks.extend(quote! {
+            if let Err(error) = v8_derive::class::expect_instance(#class, input, scope) {
+                errors.push(error);
+            }
+        });
+    }
+
+    if let (Some(tag), Some(tag_value)) = (&container_attributes.tag, &container_attributes.tag_value) {
+        checks.extend(quote! {
+            match v8_derive::get_field_as(#tag, input, scope, v8_derive::helpers::try_as_string) {
+                Ok(tag) if tag != #tag_value => errors.push(v8_derive::errors::Error::TagMismatch {
+                    expected: #tag_value.to_string(),
+                    actual: tag,
+                }),
+                Ok(_) => {}
+                Err(error) => errors.push(error),
+            }
+        });
+    }
+
+    checks
+}
+
+/// Rejects the container attributes that the struct does not support.
+fn validate_struct_attributes(
+    identifier: &syn::Ident,
+    fields: &syn::Fields,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<()> {
+    if container_attributes.discriminant_key.is_some() {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the discriminant_key attribute is only supported on enums",
+        ));
+    }
+
+    if container_attributes.class.is_some()
+        && (newtype_field(fields, container_attributes.transparent).is_some() || container_attributes.array)
+    {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the class attribute is not supported on newtype structs or with the array attribute",
+        ));
+    }
+
+    if container_attributes.case_insensitive {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the case_insensitive attribute is only supported on enums",
+        ));
+    }
+
+    if container_attributes.default
+        && (newtype_field(fields, container_attributes.transparent).is_some() || container_attributes.array)
+    {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the default attribute is not supported on newtype structs or with the array attribute",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The attributes of a field, with the `strict_numbers` container attribute applied
+fn field_attributes(field: &syn::Field, container_attributes: &ContainerAttributes) -> syn::Result<FieldAttributes> {
+    let mut attributes = FieldAttributes::from_field(field)?;
+    attributes.strict_numbers |= container_attributes.strict_numbers;
+    Ok(attributes)
+}
+
+/// Read each field from the property with the same name, as renamed by `naming`; with the `default` container
+/// attribute, a missing property leaves the field at the default of its type. With a `ctx` type, the `with_ctx`
+/// fields are read with the context, and every field is reported to it once read
+fn from_object(
+    constructor: &TokenStream,
+    fields: &syn::Fields,
+    container_attributes: &ContainerAttributes,
+    naming: FieldNaming<'_>,
+    ctx: Option<&syn::Type>,
+) -> syn::Result<TokenStream> {
+    let mut implementation = TokenStream::new();
+
+    for field in fields {
+        let Some(identifier) = field.ident.as_ref() else {
+            continue;
+        };
+
+        if is_phantom_data(field) {
+            implementation.extend(quote! {
+                #identifier: ::core::marker::PhantomData,
+            });
+            continue;
+        }
+
+        let attributes = field_attributes(field, container_attributes)?;
+        let key = quote_field_key(identifier, naming);
+        let mut value = if ctx.is_some() && attributes.with_ctx {
+            quote! { v8_derive::context::get_field_with_ctx(#key, input, scope, ctx)? }
+        } else {
+            quote_get_field_as(field, &key, &attributes, container_attributes.default)
+        };
+
+        if ctx.is_some() {
+            let name = attributes.path.as_ref().map_or(key, |path| quote! { #path });
+            value = quote! {
+                {
+                    let value = #value;
+                    v8_derive::context::ConversionContext::field_converted(ctx, #name);
+                    value
+                }
+            };
+        }
+
+        implementation.extend(quote! {
+            #identifier: #value,
+        });
+    }
+
+    Ok(quote! {
+        #constructor {
+            #implementation
+        }
+    })
+}
+
+/// Read each field from the array element at the position of the field
+fn from_array(fields: &syn::Fields, container_attributes: &ContainerAttributes) -> syn::Result<TokenStream> {
+    let length = fields.len();
+    let mut implementation = TokenStream::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let Some(identifier) = field.ident.as_ref() else {
+            continue;
+        };
+
+        let attributes = field_attributes(field, container_attributes)?;
+        if attributes.path.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "the path attribute is not supported on a struct converted from an array",
+            ));
+        }
+
+        let index = u32::try_from(index).map_err(|_| syn::Error::new_spanned(field, "too many fields"))?;
+        let value = quote_get_element_as(index, field, &attributes);
+        implementation.extend(quote! {
+            #identifier: #value,
+        });
+    }
+
+    Ok(quote! {
+        {
+            let array = v8_derive::try_as_array_of_length(input, #length)?;
+            Self {
+                #implementation
+            }
+        }
+    })
+}
+
+/// Read the variant name from the discriminant key, and the fields of that variant from the same object
+fn from_enum(
+    key: &syn::LitStr,
+    data: &syn::DataEnum,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<TokenStream> {
+    if let Some(other) = other_variant(data)? {
+        return Err(syn::Error::new_spanned(
+            other,
+            "the other attribute is only supported on enums that convert as strings",
+        ));
+    }
+
+    let mut variants = Vec::new();
+
+    for variant in &data.variants {
+        let identifier = &variant.ident;
+        let name = js_variant_name(identifier, container_attributes.rename_all);
+
+        let value = match &variant.fields {
+            syn::Fields::Named(_) => from_object(
+                &quote! { Self::#identifier },
+                &variant.fields,
+                container_attributes,
+                FieldNaming::default(),
+                None,
+            )?,
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote! {
+                    Self::#identifier(<#ty as v8_derive::TryFromValue>::try_from_value(input, scope)?)
+                }
+            }
+            syn::Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "only unit, newtype and struct variants are supported",
+                ))
+            }
+            syn::Fields::Unit => quote! { Self::#identifier },
+        };
+
+        variants.push((name, value));
+    }
+
+    let match_variant = quote_match_variant(&variants, container_attributes.case_insensitive, None);
+    Ok(quote! {
+        {
+            let variant = v8_derive::get_field_as(#key, input, scope, v8_derive::helpers::try_as_string)?;
+            #match_variant
+        }
+    })
+}
+
+/// Read the variant name from a JS string; an unknown name is held by the `other` variant, if there is one
+fn from_unit_enum(data: &syn::DataEnum, container_attributes: &ContainerAttributes) -> syn::Result<TokenStream> {
+    let other = other_variant(data)?;
+    let variants: Vec<_> = data
+        .variants
+        .iter()
+        .filter(|variant| Some(&variant.ident) != other)
+        .map(|variant| {
+            let identifier = &variant.ident;
+            let name = js_variant_name(identifier, container_attributes.rename_all);
+            (name, quote! { Self::#identifier })
+        })
+        .collect();
+
+    let match_variant = quote_match_variant(&variants, container_attributes.case_insensitive, other);
+    Ok(quote! {
+        {
+            let variant = v8_derive::helpers::try_as_string(input, scope)?;
+            #match_variant
+        }
+    })
+}
+
+/// Match the `variant` string against the variant names, ignoring ASCII case with `case_insensitive`; an unknown
+/// name goes into the `other` variant, or is rejected without one
+fn quote_match_variant(
+    variants: &[(String, TokenStream)],
+    case_insensitive: bool,
+    other: Option<&syn::Ident>,
+) -> TokenStream {
+    let unknown = other.map_or_else(
+        || quote! { return Err(v8_derive::errors::Error::UnknownVariant(variant)) },
+        |other| quote! { Self::#other(variant) },
+    );
+
+    if case_insensitive {
+        let branches = variants.iter().map(|(name, value)| {
+            quote! {
+                if variant.eq_ignore_ascii_case(#name) { #value }
+            }
+        });
+        return quote! {
+            #(#branches else)* { #unknown }
+        };
+    }
+
+    let arms = variants.iter().map(|(name, value)| quote! { #name => #value, });
+    quote! {
+        match variant.as_str() {
+            #(#arms)*
+            _ => #unknown,
+        }
+    }
+}
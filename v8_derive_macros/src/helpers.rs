@@ -1,5 +1,7 @@
-use proc_macro2::TokenStream;
+use crate::attributes::{FieldAttributes, FieldNaming, RenameRule, VariantAttributes};
+use proc_macro2::{TokenStream, TokenTree};
 use quote::quote;
+use syn::ext::IdentExt;
 
 pub(crate) fn option_type(ty: &syn::Type) -> Option<&syn::Type> {
     let syn::Type::Path(ty) = ty else { return None };
@@ -35,74 +37,481 @@ pub(crate) fn option_type(ty: &syn::Type) -> Option<&syn::Type> {
     Some(inner_type)
 }
 
+/// The JS name of a field or variant, without the `r#` prefix of a raw identifier like `r#type`
+pub(crate) fn js_name(identifier: &syn::Ident) -> String {
+    identifier.unraw().to_string()
+}
+
+/// The expression of the JS name of a field: in the casing of the `rename_all` container attribute, or the
+/// result of the `rename_with` function, which is called at runtime
+pub(crate) fn quote_field_key(identifier: &syn::Ident, naming: FieldNaming<'_>) -> TokenStream {
+    let name = js_name(identifier);
+    let name = match naming.rename_all {
+        Some(rule) => rule.apply_to_field(&name),
+        None => name,
+    };
+
+    #[allow(clippy::single_match_else)]
+    match naming.rename_with {
+        Some(rename_with) => quote! { &#rename_with(#name) },
+        None => quote! { #name },
+    }
+}
+
+/// The JS name of an enum variant, in the casing of the `rename_all` container attribute
+pub(crate) fn js_variant_name(identifier: &syn::Ident, rename_all: Option<RenameRule>) -> String {
+    let name = js_name(identifier);
+    match rename_all {
+        Some(rule) => rule.apply_to_variant(&name),
+        None => name,
+    }
+}
+
+/// Reads the field from the input object property at `key`, returning from the conversion on an error
 pub(crate) fn quote_get_field_as(
-    ident: &syn::Ident,
-    identifier: &syn::Ident,
     field: &syn::Field,
-    optional: bool,
-) -> Option<TokenStream> {
-    let get_operation = if optional {
+    key: &TokenStream,
+    attributes: &FieldAttributes,
+    default: bool,
+) -> TokenStream {
+    let result = quote_get_field_result(field, key, attributes, default);
+    quote! { #result? }
+}
+
+/// The `Result` of reading the field from the input object property at `key`; with `default`, a missing, `null`
+/// or `undefined` property of a non-`Option` field becomes the default of its type
+pub(crate) fn quote_get_field_result(
+    field: &syn::Field,
+    key: &TokenStream,
+    attributes: &FieldAttributes,
+    default: bool,
+) -> TokenStream {
+    let (parse_fn, optional) = quote_field_parse_fn(field, attributes);
+    let read_optional = optional || default;
+
+    let (get_operation, key) = match &attributes.path {
+        Some(path) if read_optional => (quote! { v8_derive::get_optional_path_as }, quote! { #path }),
+        Some(path) => (quote! { v8_derive::get_path_as }, quote! { #path }),
+        None if read_optional => (quote! { v8_derive::get_optional_field_as }, key.clone()),
+        None => (quote! { v8_derive::get_field_as }, key.clone()),
+    };
+
+    if default && !optional {
         quote! {
-            v8_derive::get_optional_field_as
+            #get_operation(#key, input, scope, #parse_fn).map(Option::unwrap_or_default)
         }
     } else {
         quote! {
-            v8_derive::get_field_as
+            #get_operation(#key, input, scope, #parse_fn)
         }
+    }
+}
+
+pub(crate) fn quote_get_element_as(index: u32, field: &syn::Field, attributes: &FieldAttributes) -> TokenStream {
+    let (parse_fn, optional) = quote_field_parse_fn(field, attributes);
+
+    let get_operation = if optional {
+        quote! { v8_derive::get_optional_element_as }
+    } else {
+        quote! { v8_derive::get_element_as }
     };
 
-    Some(if ident == "String" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_string)?
-        }
+    quote! {
+        #get_operation(#index, &array, scope, #parse_fn)?
+    }
+}
+
+/// The function used to parse the field, and whether the field is an `Option`
+fn quote_field_parse_fn(field: &syn::Field, attributes: &FieldAttributes) -> (TokenStream, bool) {
+    match option_type(&field.ty) {
+        Some(inner_type) => (quote_parse_fn(inner_type, attributes), true),
+        None => (quote_parse_fn(&field.ty, attributes), false),
+    }
+}
+
+const INTEGER_TYPES: [&str; 12] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Whether the type is one of the named primitive types
+fn is_type(ty: &syn::Type, names: &[&str]) -> bool {
+    let syn::Type::Path(type_path) = ty else {
+        return false;
+    };
+
+    let ident = get_ident(type_path);
+    names.iter().any(|name| ident == name)
+}
+
+/// The function used to parse a JS value into the given type
+pub(crate) fn quote_parse_fn(ty: &syn::Type, attributes: &FieldAttributes) -> TokenStream {
+    if attributes.skip_holes {
+        return quote! { v8_derive::helpers::try_as_vec_skip_holes };
+    }
+
+    if attributes.iterable {
+        return quote! { v8_derive::helpers::try_as_vec_iterable };
+    }
+
+    if attributes.preserve_key_order {
+        return quote! { v8_derive::helpers::try_as_ordered_pairs };
+    }
+
+    if let Some(names) = &attributes.fields {
+        return quote_parse_named_tuple(ty, names);
+    }
+
+    let syn::Type::Path(type_path) = ty else {
+        return quote! { <#ty as v8_derive::TryFromValue>::try_from_value };
+    };
+
+    let ident = get_ident(type_path);
+
+    if attributes.coerce {
+        return if ident == "i32" {
+            quote! { v8_derive::helpers::try_as_i32_coerce }
+        } else if ident == "u32" {
+            quote! { v8_derive::helpers::try_as_u32_coerce }
+        } else {
+            syn::Error::new_spanned(ty, "the coerce attribute is only supported on i32 and u32 fields")
+                .to_compile_error()
+        };
+    }
+
+    if attributes.numeric_string {
+        return if is_type(ty, &INTEGER_TYPES) {
+            quote! { v8_derive::helpers::try_as_numeric_string }
+        } else {
+            syn::Error::new_spanned(ty, "the numeric_string attribute is only supported on integer fields")
+                .to_compile_error()
+        };
+    }
+
+    if attributes.as_string {
+        return if ident == "Vec" {
+            quote! { v8_derive::helpers::try_as_char_vec }
+        } else {
+            syn::Error::new_spanned(ty, "the as_string attribute is only supported on Vec<char> fields")
+                .to_compile_error()
+        };
+    }
+
+    // a field converted into a number has to accept it back
+    let strict_numbers = attributes.strict_numbers || attributes.number_if_safe;
+
+    if strict_numbers && ident == "i64" {
+        return quote! { v8_derive::helpers::try_as_i64_strict };
+    }
+
+    if strict_numbers && ident == "u64" {
+        return quote! { v8_derive::helpers::try_as_u64_strict };
+    }
+
+    if ident == "String" {
+        quote! { v8_derive::helpers::try_as_string }
     } else if ident == "bool" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_bool)?
-        }
+        quote! { v8_derive::helpers::try_as_bool }
     } else if ident == "i8" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_i8)?
-        }
+        quote! { v8_derive::helpers::try_as_i8 }
     } else if ident == "i32" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_i32)?
-        }
+        quote! { v8_derive::helpers::try_as_i32 }
     } else if ident == "i64" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_i64)?
-        }
+        quote! { v8_derive::helpers::try_as_i64 }
     } else if ident == "f64" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_f64)?
-        }
+        quote! { v8_derive::helpers::try_as_f64 }
     } else if ident == "f32" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_f32)?
-        }
+        quote! { v8_derive::helpers::try_as_f32 }
+    } else if ident == "u8" {
+        quote! { v8_derive::helpers::try_as_u8 }
+    } else if ident == "u16" {
+        quote! { v8_derive::helpers::try_as_u16 }
     } else if ident == "u32" {
-        quote! {
-            #get_operation(stringify!(#identifier), input, scope, v8_derive::helpers::try_as_u32)?
+        quote! { v8_derive::helpers::try_as_u32 }
+    } else if ident == "u64" {
+        quote! { v8_derive::helpers::try_as_u64 }
+    } else {
+        // Assume the type implements `TryFromValue`, e.g. a derived struct
+        quote! { <#ty as v8_derive::TryFromValue>::try_from_value }
+    }
+}
+
+/// The element types of a tuple field with the `fields` attribute, which must have one name per element
+fn named_tuple_elements<'a>(ty: &'a syn::Type, names: &[syn::LitStr]) -> syn::Result<Vec<&'a syn::Type>> {
+    match ty {
+        syn::Type::Tuple(tuple) if tuple.elems.len() == names.len() => Ok(tuple.elems.iter().collect()),
+        _ => Err(syn::Error::new_spanned(
+            ty,
+            "the fields attribute requires a tuple with one name per element",
+        )),
+    }
+}
+
+/// Reads each element of the tuple from the property with the name at its position
+fn quote_parse_named_tuple(ty: &syn::Type, names: &[syn::LitStr]) -> TokenStream {
+    let elements = match named_tuple_elements(ty, names) {
+        Ok(elements) => elements,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    quote! {
+        {
+            let parse_fn: v8_derive::helpers::ParseFn<#ty> = |input, scope| {
+                Ok((
+                    #(v8_derive::get_field_as(#names, input, scope, <#elements as v8_derive::TryFromValue>::try_from_value)?,)*
+                ))
+            };
+            parse_fn
+        }
+    }
+}
+
+/// Sets each element of the tuple, held by `value`, as the property with the name at its position
+fn quote_into_named_tuple(ty: &syn::Type, names: &[syn::LitStr], value: &TokenStream) -> TokenStream {
+    let elements = match named_tuple_elements(ty, names) {
+        Ok(elements) => elements,
+        Err(err) => return err.to_compile_error(),
+    };
+    let indices = (0..elements.len()).map(syn::Index::from);
+
+    quote! {
+        {
+            let tuple = #value;
+            let tuple_object = v8::Object::new(scope);
+            #(
+                let js_key = v8::String::new(scope, #names).unwrap().into();
+                let js_val = tuple.#indices.into_value(scope);
+                tuple_object.set(scope, js_key, js_val);
+            )*
+            tuple_object.into()
         }
-    } else if ident == "Option" {
-        let o_type = option_type(&field.ty)?;
+    }
+}
+
+/// The expression that converts the field, held by `value`, into a JS value
+pub(crate) fn quote_into_value(field: &syn::Field, attributes: &FieldAttributes, value: &TokenStream) -> TokenStream {
+    let numeric_string = |number: TokenStream| quote! { v8::String::new(scope, &#number.to_string()).unwrap().into() };
 
-        let syn::Type::Path(type_path) = o_type else {
-            return None;
+    let (ty, optional) = match option_type(&field.ty) {
+        Some(inner_type) => (inner_type, true),
+        None => (&field.ty, false),
+    };
+
+    if let Some(names) = &attributes.fields {
+        return if optional {
+            let convert = quote_into_named_tuple(ty, names, &quote! { tuple });
+            quote! {
+                match #value {
+                    Some(tuple) => #convert,
+                    None => v8::null(scope).into(),
+                }
+            }
+        } else {
+            quote_into_named_tuple(ty, names, value)
         };
+    }
 
-        let ident = get_ident(type_path);
-        quote_get_field_as(ident, identifier, field, true)?
+    if attributes.sort_keys {
+        return if optional {
+            quote! {
+                match #value {
+                    Some(map) => v8_derive::into::IntoSorted::into_value_sorted(map, scope),
+                    None => v8::null(scope).into(),
+                }
+            }
+        } else {
+            quote! { v8_derive::into::IntoSorted::into_value_sorted(#value, scope) }
+        };
+    }
+
+    if attributes.number_if_safe {
+        return quote_into_number_if_safe(ty, optional, value);
+    }
+
+    if attributes.preserve_key_order {
+        return if optional {
+            quote! {
+                match #value {
+                    Some(pairs) => v8_derive::helpers::ordered_pairs_into_value(pairs, scope),
+                    None => v8::null(scope).into(),
+                }
+            }
+        } else {
+            quote! { v8_derive::helpers::ordered_pairs_into_value(#value, scope) }
+        };
+    }
+
+    if attributes.as_string {
+        return if optional {
+            quote! {
+                match &#value {
+                    Some(chars) => v8_derive::helpers::char_vec_into_value(chars, scope),
+                    None => v8::null(scope).into(),
+                }
+            }
+        } else {
+            quote! { v8_derive::helpers::char_vec_into_value(&#value, scope) }
+        };
+    }
+
+    if !attributes.numeric_string {
+        return quote! { #value.into_value(scope) };
+    }
+
+    if optional {
+        let convert = numeric_string(quote! { (*number) });
+        quote! {
+            match &#value {
+                Some(number) => #convert,
+                None => v8::null(scope).into(),
+            }
+        }
     } else {
-        // Assume the type is a struct and try to convert it
+        numeric_string(value.clone())
+    }
+}
+
+/// The expression that converts the `i64` or `u64` field into a JS number when it is a safe integer
+fn quote_into_number_if_safe(ty: &syn::Type, optional: bool, value: &TokenStream) -> TokenStream {
+    let convert = if is_type(ty, &["i64"]) {
+        quote! { v8_derive::helpers::i64_into_value_if_safe }
+    } else if is_type(ty, &["u64"]) {
+        quote! { v8_derive::helpers::u64_into_value_if_safe }
+    } else {
+        return syn::Error::new_spanned(
+            ty,
+            "the number_if_safe attribute is only supported on i64 and u64 fields",
+        )
+        .to_compile_error();
+    };
+
+    if optional {
         quote! {
-            #get_operation(stringify!(#identifier), input, scope, #ident::try_from_value)?
+            match &#value {
+                Some(number) => #convert(*number, scope),
+                None => v8::null(scope).into(),
+            }
         }
+    } else {
+        quote! { #convert(#value, scope) }
+    }
+}
+
+/// Whether the field is a `PhantomData` marker, which is not converted
+pub(crate) fn is_phantom_data(field: &syn::Field) -> bool {
+    let syn::Type::Path(type_path) = &field.ty else {
+        return false;
+    };
+
+    type_path.qself.is_none() && get_ident(type_path) == "PhantomData"
+}
+
+/// The generics of the input, with a `bound` on the type of every converted field that uses a type parameter,
+/// e.g. `T: v8_derive::TryFromValue` for a field of type `T`; the predicates of a `#[v8(bound = "...")]`
+/// attribute are used verbatim instead
+pub(crate) fn bounded_generics(
+    input: &syn::DeriveInput,
+    bound: &TokenStream,
+    custom: Option<&[syn::WherePredicate]>,
+) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    if let Some(predicates) = custom {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(predicates.iter().cloned());
+        return generics;
+    }
+
+    let parameters: Vec<&syn::Ident> = input.generics.type_params().map(|parameter| &parameter.ident).collect();
+    if parameters.is_empty() {
+        return generics;
+    }
+
+    let fields: Vec<&syn::Field> = match &input.data {
+        syn::Data::Struct(data) => data.fields.iter().collect(),
+        syn::Data::Enum(data) => data.variants.iter().flat_map(|variant| variant.fields.iter()).collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        if !is_phantom_data(field) && mentions(quote! { #ty }, &parameters) {
+            where_clause.predicates.push(syn::parse_quote! { #ty: #bound });
+        }
+    }
+
+    generics
+}
+
+/// Whether any of the identifiers occurs in the tokens
+fn mentions(tokens: TokenStream, identifiers: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => identifiers.iter().any(|identifier| **identifier == ident),
+        TokenTree::Group(group) => mentions(group.stream(), identifiers),
+        _ => false,
     })
 }
 
+/// The field of a newtype struct, e.g. `struct UserId(u64)`, which is converted as its inner value; with the
+/// `transparent` container attribute also the single field of a struct with named fields
+pub(crate) fn newtype_field(fields: &syn::Fields, transparent: bool) -> Option<&syn::Field> {
+    match fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed.first(),
+        syn::Fields::Named(fields) if transparent && fields.named.len() == 1 => fields.named.first(),
+        _ => None,
+    }
+}
+
+/// The member that accesses the field returned by `newtype_field`, e.g. `0` or `value`
+pub(crate) fn newtype_member(field: &syn::Field) -> syn::Member {
+    field
+        .ident
+        .clone()
+        .map_or_else(|| syn::Member::from(0), syn::Member::Named)
+}
+
+/// Whether none of the variants of the enum has fields, so it can be converted as a string; the `other` variant
+/// holds the string itself
+pub(crate) fn is_unit_enum(data: &syn::DataEnum) -> bool {
+    data.variants.iter().all(|variant| {
+        matches!(variant.fields, syn::Fields::Unit)
+            || VariantAttributes::from_variant(variant).is_ok_and(|attributes| attributes.other)
+    })
+}
+
+/// The variant with the `other` attribute, which holds the name of an unknown variant
+pub(crate) fn other_variant(data: &syn::DataEnum) -> syn::Result<Option<&syn::Ident>> {
+    let mut other = None;
+
+    for variant in &data.variants {
+        if !VariantAttributes::from_variant(variant)?.other {
+            continue;
+        }
+
+        if !matches!(&variant.fields, syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "the other attribute requires a newtype variant holding a String",
+            ));
+        }
+
+        if other.is_some() {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "only one variant can have the other attribute",
+            ));
+        }
+
+        other = Some(&variant.ident);
+    }
+
+    Ok(other)
+}
+
 pub(crate) fn get_ident(type_path: &syn::TypePath) -> &syn::Ident {
     let path = &type_path.path;
     // todo: fix unwrap
-    let segment = path.segments.first().unwrap();
+    let segment = path.segments.last().unwrap();
     (&segment.ident) as _
 }
@@ -0,0 +1,511 @@
+//! Implementation of the `IntoValue` derive
+
+use crate::{
+    attributes::{ContainerAttributes, FieldAttributes, FieldNaming, RenameRule},
+    helpers::{
+        bounded_generics, is_phantom_data, is_unit_enum, js_variant_name, newtype_field, newtype_member, other_variant,
+        quote_field_key, quote_into_value,
+    },
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Data;
+
+pub(crate) fn derive(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let identifier = &input.ident;
+    let container_attributes = ContainerAttributes::from_input(input)?;
+
+    let generics = bounded_generics(
+        input,
+        &quote! { v8_derive::IntoValue },
+        container_attributes.into_bound.as_deref(),
+    );
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let mut extend_object = None;
+
+    let implementation = match &input.data {
+        Data::Struct(syn::DataStruct { fields, .. }) => {
+            validate_struct_attributes(identifier, fields, &container_attributes)?;
+
+            if let Some(field) = newtype_field(fields, container_attributes.transparent) {
+                let member = newtype_member(field);
+                quote_into_value(field, &FieldAttributes::from_field(field)?, &quote! { self.#member })
+            } else if container_attributes.array {
+                into_array(fields)?
+            } else {
+                let new_object = quote_new_object(&container_attributes);
+                let naming = container_attributes.field_naming();
+                extend_object = Some(quote_extend_object(
+                    &generics,
+                    identifier,
+                    fields,
+                    &container_attributes,
+                )?);
+                into_object(
+                    &new_object,
+                    fields,
+                    naming,
+                    None,
+                    |identifier| quote! { self.#identifier },
+                )?
+            }
+        }
+        Data::Enum(data) => {
+            if container_attributes.array
+                || container_attributes.class.is_some()
+                || container_attributes.null_prototype
+                || container_attributes.rename_with.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    identifier,
+                    "the array, class, null_prototype and rename_with attributes are not supported on enums",
+                ));
+            }
+
+            match &container_attributes.discriminant_key {
+                Some(key) => into_enum(key, data, container_attributes.rename_all)?,
+                None if is_unit_enum(data) => into_unit_enum(data, container_attributes.rename_all)?,
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        identifier,
+                        "enums with fields require the discriminant_key attribute",
+                    ))
+                }
+            }
+        }
+        Data::Union(_) => panic!("Only structs and enums are supported"),
+    };
+
+    let implementation = quote_wrap_object(implementation, &container_attributes);
+    let with_ctx = quote_with_ctx(input, &generics, &container_attributes)?;
+
+    // the with_ctx fields cannot be converted without a context
+    if container_attributes.with_ctx_fields {
+        return Ok(with_ctx.unwrap_or_default());
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics v8_derive::IntoValue for #identifier #type_generics #where_clause {
+            fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
+                #implementation
+            }
+        }
+
+        #extend_object
+        #with_ctx
+    })
+}
+
+/// Implement `IntoValueWithCtx` for the context type of the `ctx` attribute, which sets the fields like
+/// `IntoValue` does and reports each of them to the context
+fn quote_with_ctx(
+    input: &syn::DeriveInput,
+    generics: &syn::Generics,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<Option<TokenStream>> {
+    let (Some(ctx), Data::Struct(syn::DataStruct { fields, .. })) = (&container_attributes.ctx, &input.data) else {
+        return Ok(None);
+    };
+
+    let identifier = &input.ident;
+    let new_object = quote_new_object(container_attributes);
+    let implementation = into_object(
+        &new_object,
+        fields,
+        container_attributes.field_naming(),
+        Some(ctx),
+        |identifier| quote! { self.#identifier },
+    )?;
+    let implementation = quote_wrap_object(implementation, container_attributes);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(Some(quote! {
+        #[automatically_derived]
+        impl #impl_generics v8_derive::context::IntoValueWithCtx<#ctx> for #identifier #type_generics #where_clause {
+            fn into_value_with_ctx<'s>(
+                self,
+                scope: &mut v8::PinScope<'s, '_>,
+                ctx: &mut #ctx,
+            ) -> v8::Local<'s, v8::Value> {
+                use v8_derive::IntoValue as _;
+                #implementation
+            }
+        }
+    }))
+}
+
+/// Freeze the converted object with the `freeze` attribute, and then wrap it in a `Proxy` with the handler of the
+/// `proxy` attribute; a value that is not an object is left as is
+fn quote_wrap_object(implementation: TokenStream, container_attributes: &ContainerAttributes) -> TokenStream {
+    let implementation = if container_attributes.freeze {
+        quote! {
+            let value: v8::Local<'s, v8::Value> = {
+                #implementation
+            };
+            if let Ok(object) = v8::Local::<v8::Object>::try_from(value) {
+                object.set_integrity_level(scope, v8::IntegrityLevel::Frozen);
+            }
+            value
+        }
+    } else {
+        implementation
+    };
+
+    match &container_attributes.proxy {
+        Some(handler) => quote! {
+            let value: v8::Local<'s, v8::Value> = {
+                #implementation
+            };
+            let Ok(target) = v8::Local::<v8::Object>::try_from(value) else {
+                return value;
+            };
+            let handler: v8::Local<'s, v8::Object> = #handler(scope);
+            match v8::Proxy::new(scope, target, handler) {
+                Some(proxy) => proxy.into(),
+                None => value,
+            }
+        },
+        None => implementation,
+    }
+}
+
+/// Rejects the container attributes that the struct does not support.
+fn validate_struct_attributes(
+    identifier: &syn::Ident,
+    fields: &syn::Fields,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<()> {
+    if container_attributes.discriminant_key.is_some() {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the discriminant_key attribute is only supported on enums",
+        ));
+    }
+
+    if container_attributes.case_insensitive {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the case_insensitive attribute is only supported on enums",
+        ));
+    }
+
+    if container_attributes.class.is_some()
+        && (newtype_field(fields, container_attributes.transparent).is_some() || container_attributes.array)
+    {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the class attribute is not supported on newtype structs or with the array attribute",
+        ));
+    }
+
+    if container_attributes.null_prototype
+        && (newtype_field(fields, container_attributes.transparent).is_some()
+            || container_attributes.array
+            || container_attributes.class.is_some())
+    {
+        return Err(syn::Error::new_spanned(
+            identifier,
+            "the null_prototype attribute is not supported on newtype structs, or with the array or class \
+             attribute",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Create the object that a struct converts into: an instance of the registered class, an object without a
+/// prototype, or a plain object; with the `tag` attribute, the object already holds the tag
+fn quote_new_object(container_attributes: &ContainerAttributes) -> TokenStream {
+    let new_object = match &container_attributes.class {
+        Some(class) => quote! {
+            v8_derive::class::new_instance(#class, scope).unwrap_or_else(|| v8::Object::new(scope))
+        },
+        None if container_attributes.null_prototype => quote! {
+            {
+                let object = v8::Object::new(scope);
+                let prototype = v8::null(scope).into();
+                object.set_prototype(scope, prototype);
+                object
+            }
+        },
+        None => quote! { v8::Object::new(scope) },
+    };
+
+    // the tag is set first, so it is also the first key of the object
+    match quote_set_tag(container_attributes) {
+        Some(set_tag) => quote! {
+            {
+                let object = #new_object;
+                #set_tag
+                object
+            }
+        },
+        None => new_object,
+    }
+}
+
+/// Set the constant `tag` property of a struct with the `tag` and `tag_value` attributes on `object`
+fn quote_set_tag(container_attributes: &ContainerAttributes) -> Option<TokenStream> {
+    let (Some(tag), Some(tag_value)) = (&container_attributes.tag, &container_attributes.tag_value) else {
+        return None;
+    };
+
+    Some(quote! {
+        let tag = v8::String::new(scope, #tag).unwrap().into();
+        let tag_value = v8::String::new(scope, #tag_value).unwrap().into();
+        object.set(scope, tag, tag_value);
+    })
+}
+
+/// Implement `ExtendObject` for a struct that converts into an object, setting its fields on an existing object
+fn quote_extend_object(
+    generics: &syn::Generics,
+    identifier: &syn::Ident,
+    fields: &syn::Fields,
+    container_attributes: &ContainerAttributes,
+) -> syn::Result<TokenStream> {
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let naming = container_attributes.field_naming();
+    let set_tag = quote_set_tag(container_attributes);
+    let set_fields = quote_set_fields(fields, naming, None, |identifier| quote! { self.#identifier })?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics v8_derive::into::ExtendObject for #identifier #type_generics #where_clause {
+            fn write_into<'s>(self, object: v8::Local<'s, v8::Object>, scope: &mut v8::PinScope<'s, '_>) {
+                use v8_derive::IntoValue as _;
+                #set_tag
+                #set_fields
+            }
+        }
+    })
+}
+
+/// Set each field as a property with the same name on the object created by `new_object`; `access` yields the
+/// expression that holds the field
+fn into_object(
+    new_object: &TokenStream,
+    fields: &syn::Fields,
+    naming: FieldNaming<'_>,
+    ctx: Option<&syn::Type>,
+    access: impl Fn(&syn::Ident) -> TokenStream,
+) -> syn::Result<TokenStream> {
+    let set_fields = quote_set_fields(fields, naming, ctx, access)?;
+
+    Ok(quote! {
+        let object = #new_object;
+        #set_fields
+        object.into()
+    })
+}
+
+/// The fields are set in declaration order, which JS keeps as the property order of the object; the property
+/// names are renamed by `naming`. With a `ctx` type, the `with_ctx` fields are converted with the context, and
+/// every field that is set is reported to it
+fn quote_set_fields(
+    fields: &syn::Fields,
+    naming: FieldNaming<'_>,
+    ctx: Option<&syn::Type>,
+    access: impl Fn(&syn::Ident) -> TokenStream,
+) -> syn::Result<TokenStream> {
+    let mut implementation = TokenStream::new();
+
+    for field in fields {
+        let Some(identifier) = field.ident.as_ref() else {
+            continue;
+        };
+
+        if is_phantom_data(field) {
+            continue;
+        }
+
+        let attributes = FieldAttributes::from_field(field)?;
+        let key = quote_field_key(identifier, naming);
+        let value = access(identifier);
+        let into_value = if ctx.is_some() && attributes.with_ctx {
+            quote! { v8_derive::context::IntoValueWithCtx::into_value_with_ctx(#value, scope, ctx) }
+        } else {
+            quote_into_value(field, &attributes, &value)
+        };
+
+        #[allow(clippy::single_match_else)]
+        let mut field_impl = match &attributes.path {
+            Some(path) => quote! {
+                let js_val: v8::Local<v8::Value> = #into_value;
+                v8_derive::set_path(#path, object, js_val, scope);
+            },
+            None => quote! {
+                let js_key = v8::String::new(scope, #key).unwrap().into();
+                let js_val: v8::Local<v8::Value> = #into_value;
+                object.set(scope, js_key, js_val);
+            },
+        };
+
+        if ctx.is_some() {
+            let name = attributes.path.as_ref().map_or(key, |path| quote! { #path });
+            field_impl.extend(quote! {
+                v8_derive::context::ConversionContext::field_converted(ctx, #name);
+            });
+        }
+
+        if let Some(predicate) = &attributes.skip_serializing_if {
+            field_impl = quote! {
+                if !#predicate(&#value) {
+                    #field_impl
+                }
+            };
+        }
+
+        if attributes.skip_serializing_if_none {
+            field_impl = quote! {
+                if #value.is_some() {
+                    #field_impl
+                }
+            };
+        }
+
+        implementation.extend(field_impl);
+    }
+
+    Ok(implementation)
+}
+
+/// Set each field as the array element at the position of the field
+fn into_array(fields: &syn::Fields) -> syn::Result<TokenStream> {
+    let length = i32::try_from(fields.len()).map_err(|_| syn::Error::new_spanned(fields, "too many fields"))?;
+    let mut implementation = TokenStream::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let Some(identifier) = field.ident.as_ref() else {
+            continue;
+        };
+
+        let attributes = FieldAttributes::from_field(field)?;
+        if attributes.path.is_some() || attributes.skip_serializing_if.is_some() || attributes.skip_serializing_if_none
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "the path and skip_serializing_if attributes are not supported on a struct converted into an array",
+            ));
+        }
+
+        let index = u32::try_from(index).map_err(|_| syn::Error::new_spanned(field, "too many fields"))?;
+        let into_value = quote_into_value(field, &attributes, &quote! { self.#identifier });
+        implementation.extend(quote! {
+            let js_val: v8::Local<v8::Value> = #into_value;
+            array.set_index(scope, #index, js_val);
+        });
+    }
+
+    Ok(quote! {
+        let array = v8::Array::new(scope, #length);
+        #implementation
+        array.into()
+    })
+}
+
+/// Set the variant name as the discriminant key, next to the fields of the variant
+fn into_enum(key: &syn::LitStr, data: &syn::DataEnum, rename_all: Option<RenameRule>) -> syn::Result<TokenStream> {
+    if let Some(other) = other_variant(data)? {
+        return Err(syn::Error::new_spanned(
+            other,
+            "the other attribute is only supported on enums that convert as strings",
+        ));
+    }
+
+    let mut arms = TokenStream::new();
+
+    for variant in &data.variants {
+        let identifier = &variant.ident;
+        let name = js_variant_name(identifier, rename_all);
+
+        let set_discriminant = quote! {
+            let js_key = v8_derive::intern::interned_string(scope, #key);
+            let js_val = v8_derive::intern::interned_string(scope, #name);
+            object.set(scope, js_key, js_val);
+        };
+
+        let arm = match &variant.fields {
+            syn::Fields::Named(fields) => {
+                let bindings = fields
+                    .named
+                    .iter()
+                    .filter(|field| !is_phantom_data(field))
+                    .map(|field| &field.ident);
+                let set_fields = quote_set_fields(
+                    &variant.fields,
+                    FieldNaming::default(),
+                    None,
+                    |identifier| quote! { #identifier },
+                )?;
+                quote! {
+                    Self::#identifier { #(#bindings,)* .. } => {
+                        let object = v8::Object::new(scope);
+                        #set_discriminant
+                        #set_fields
+                        object.into()
+                    }
+                }
+            }
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                Self::#identifier(inner) => {
+                    let value = inner.into_value(scope);
+                    if let Ok(object) = v8::Local::<v8::Object>::try_from(value) {
+                        #set_discriminant
+                    }
+                    value
+                }
+            },
+            syn::Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "only unit, newtype and struct variants are supported",
+                ))
+            }
+            syn::Fields::Unit => quote! {
+                Self::#identifier => {
+                    let object = v8::Object::new(scope);
+                    #set_discriminant
+                    object.into()
+                }
+            },
+        };
+
+        arms.extend(arm);
+    }
+
+    Ok(quote! {
+        match self {
+            #arms
+        }
+    })
+}
+
+/// Convert the variant into a JS string with its name, interned per isolate, or with the name held by the `other`
+/// variant
+fn into_unit_enum(data: &syn::DataEnum, rename_all: Option<RenameRule>) -> syn::Result<TokenStream> {
+    let other = other_variant(data)?;
+    let mut arms = TokenStream::new();
+
+    for variant in &data.variants {
+        let identifier = &variant.ident;
+        if Some(identifier) == other {
+            arms.extend(quote! {
+                Self::#identifier(name) => v8::String::new(scope, name).unwrap().into(),
+            });
+            continue;
+        }
+
+        let name = js_variant_name(identifier, rename_all);
+        arms.extend(quote! {
+            Self::#identifier => v8_derive::intern::interned_string(scope, #name),
+        });
+    }
+
+    Ok(quote! {
+        match &self {
+            #arms
+        }
+    })
+}
@@ -1,126 +1,74 @@
 #![warn(clippy::pedantic)]
 
+mod attributes;
+mod from_value;
 mod helpers;
+mod into_value;
 
 extern crate proc_macro2;
 
-use helpers::{get_ident, quote_get_field_as};
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Data;
-
-/// Derive `TryFromValue` for a struct
+/// Derive `TryFromValue` for a struct, a unit-only enum, or an enum with a `discriminant_key`
+///
+/// The where clause requires every field type that mentions a type parameter to implement `TryFromValue`. For a
+/// recursive type that bound requires the impl itself, so `bound` replaces it:
+///
+/// ```
+/// use v8_derive::{macros::FromValue, TryFromValue};
+///
+/// #[derive(FromValue)]
+/// #[v8(bound = "T: v8_derive::TryFromValue")]
+/// struct Tree<T> {
+///     value: T,
+///     children: Vec<Tree<T>>,
+/// }
+///
+/// fn read(
+///     input: &v8::Local<'_, v8::Value>,
+///     scope: &mut v8::PinScope<'_, '_>,
+/// ) -> v8_derive::errors::Result<Tree<i32>> {
+///     Tree::try_from_value(input, scope)
+/// }
+/// ```
+///
+/// Without it, `Tree<i32>` does not implement `TryFromValue`:
+///
+/// ```compile_fail,E0599
+/// use v8_derive::{macros::FromValue, TryFromValue};
+///
+/// #[derive(FromValue)]
+/// struct Tree<T> {
+///     value: T,
+///     children: Vec<Tree<T>>,
+/// }
+///
+/// fn read(
+///     input: &v8::Local<'_, v8::Value>,
+///     scope: &mut v8::PinScope<'_, '_>,
+/// ) -> v8_derive::errors::Result<Tree<i32>> {
+///     Tree::try_from_value(input, scope)
+/// }
+/// ```
 ///
 /// # Panics
-/// When the input is not a struct
-#[proc_macro_derive(FromValue)]
+/// When the input is a union
+#[proc_macro_derive(FromValue, attributes(v8))]
 pub fn try_from_value(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
 
-    let struct_identifier = &input.ident;
-
-    #[allow(clippy::single_match_else)]
-    match &input.data {
-        Data::Struct(syn::DataStruct { fields, .. }) => {
-            let mut implementation = TokenStream::new();
-            implementation.extend(quote! {});
-
-            for field in fields {
-                let Some(identifier) = field.ident.as_ref() else {
-                    continue;
-                };
-
-                let field_impl = match &field.ty {
-                    syn::Type::Path(type_path) => {
-                        let ident = get_ident(type_path);
-
-                        match quote_get_field_as(ident, identifier, field, false) {
-                            Some(value) => {
-                                quote! {
-                                    #identifier: #value,
-                                }
-                            }
-                            None => continue,
-                        }
-                    }
-                    _ => unimplemented!(),
-                };
-
-                implementation.extend(field_impl);
-            }
-
-            quote! {
-                #[automatically_derived]
-                impl v8_derive::TryFromValue for #struct_identifier {
-                    fn try_from_value(
-                        input: &v8::Local<'_, v8::Value>,
-                        scope: &mut v8::PinScope<'_, '_>,
-                    ) -> v8_derive::errors::Result<Self>
-                    where
-                        Self: Sized {
-                            Ok(Self {
-                                #implementation
-                            })
-                    }
-                }
-            }
-        }
-        _ => {
-            panic!("Only structs are supported");
-        }
-    }
-    .into()
+    from_value::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-/// Derive `IntoValue` for a struct
+/// Derive `IntoValue` for a struct, a unit-only enum, or an enum with a `discriminant_key`
 ///
 /// # Panics
-/// When the input is not a struct
-#[proc_macro_derive(IntoValue)]
+/// When the input is a union
+#[proc_macro_derive(IntoValue, attributes(v8))]
 pub fn into_value(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(item as syn::DeriveInput);
 
-    let struct_identifier = &input.ident;
-
-    #[allow(clippy::single_match_else)]
-    match &input.data {
-        Data::Struct(syn::DataStruct { fields, .. }) => {
-            let mut implementation = TokenStream::new();
-            implementation.extend(quote! {});
-
-            for field in fields {
-                let Some(identifier) = field.ident.as_ref() else {
-                    continue;
-                };
-
-                let field_impl = match &field.ty {
-                    syn::Type::Path(_type_path) => {
-                        quote! {
-                            let js_key = v8::String::new(scope, stringify!(#identifier)).unwrap().into();
-                            let js_val = self.#identifier.into_value(scope);
-                            object.set(scope, js_key, js_val);
-                        }
-                    }
-                    _ => unimplemented!(),
-                };
-
-                implementation.extend(field_impl);
-            }
-
-            quote! {
-                #[automatically_derived]
-                impl v8_derive::IntoValue for #struct_identifier {
-                    fn into_value<'s>(self, scope: &mut v8::PinScope<'s, '_>) -> v8::Local<'s, v8::Value> {
-                        let object = v8::Object::new(scope);
-                        #implementation
-                        object.into()
-                    }
-                }
-            }
-        }
-        _ => {
-            panic!("Only structs are supported");
-        }
-    }
-    .into()
+    into_value::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }